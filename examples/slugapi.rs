@@ -1,4 +1,5 @@
 //! SlugAPI is a module that provides a unified interface for encoding and decoding various formats such as Hex, Base32, Base58, and Base64 in a constant-time manner. It is designed to be efficient and secure, making it suitable for cryptographic applications.
+#![allow(unused_variables, dead_code, clippy::needless_borrows_for_generic_args)]
 
 // Struct
 use slugencode::prelude::*;
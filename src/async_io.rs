@@ -0,0 +1,261 @@
+//! # Async Streaming I/O (Tokio)
+//!
+//! Requires the `tokio` feature.
+//!
+//! `AsyncSlugEncoderWriter` and `AsyncSlugDecoderReader` are the `tokio::io::AsyncWrite`/`tokio::io::AsyncRead` versions of `SlugEncoderWriter` and `SlugDecoderReader`. They are created with `SlugEncodingUsage::async_encoder_writer`/`async_decoder_reader` and support the same encodings.
+
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::errors::SlugEncodingError;
+use crate::io::{io_error, TAKEN};
+use crate::stream::{SlugStreamDecoder, SlugStreamEncoder};
+use crate::{wipe, SlugEncodingUsage};
+
+/// Size of the internal buffers.
+const BUFFER_SIZE: usize = 1024;
+
+/// # AsyncSlugEncoderWriter
+///
+/// Encodes everything written to it and writes the encoded string to `W`.
+///
+/// Encoded output is buffered until the next write, `flush()` or `shutdown()`. A trailing partial block is kept back until `shutdown()` encodes (and pads) it, so the writer must be shut down once all the data is written. The internal buffer is wiped on drop.
+pub struct AsyncSlugEncoderWriter<W: AsyncWrite + Unpin> {
+    /// Only taken by `into_inner()`, so it is always `Some` otherwise
+    inner: Option<W>,
+    encoder: SlugStreamEncoder,
+    buffer: [u8;BUFFER_SIZE],
+    buffer_pos: usize,
+    buffer_len: usize,
+    finished: bool,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncSlugEncoderWriter<W> {
+    pub(crate) fn new(usage: SlugEncodingUsage, inner: W) -> Result<Self, SlugEncodingError> {
+        return Ok(Self {
+            inner: Some(inner),
+            encoder: SlugStreamEncoder::new(usage)?,
+            buffer: [0u8;BUFFER_SIZE],
+            buffer_pos: 0,
+            buffer_len: 0,
+            finished: false,
+        })
+    }
+    /// Gets a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        return self.inner.as_ref().expect(TAKEN)
+    }
+    /// Consumes the writer, returning the underlying writer (anything not yet flushed is lost)
+    pub fn into_inner(mut self) -> W {
+        return self.inner.take().expect(TAKEN)
+    }
+    /// Gets the underlying writer pinned for polling
+    fn pin_inner(&mut self) -> Pin<&mut W> {
+        return Pin::new(self.inner.as_mut().expect(TAKEN))
+    }
+    /// Writes the buffered output to the underlying writer.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.buffer_pos < self.buffer_len {
+            let inner = Pin::new(self.inner.as_mut().expect(TAKEN));
+            let written = ready!(inner.poll_write(cx, &self.buffer[self.buffer_pos..self.buffer_len]))?;
+
+            if written == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()))
+            }
+            wipe(&mut self.buffer[self.buffer_pos..self.buffer_pos + written]);
+            self.buffer_pos += written;
+        }
+        (self.buffer_pos, self.buffer_len) = (0, 0);
+        return Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncSlugEncoderWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if this.finished {
+            return Poll::Ready(Err(io::Error::new(io::ErrorKind::BrokenPipe, "write after shutdown")))
+        }
+        ready!(this.poll_drain(cx))?;
+
+        let (consumed, written) = this.encoder.update_partial(buf, &mut this.buffer).map_err(io_error)?;
+        this.buffer_len = written;
+        return Poll::Ready(Ok(consumed))
+    }
+    /// Flushes the buffered output and the underlying writer (a partial block is only written by `shutdown()`).
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_drain(cx))?;
+        return this.pin_inner().poll_flush(cx)
+    }
+    /// Encodes (and pads) the last partial block, then shuts down the underlying writer.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_drain(cx))?;
+
+        if !this.finished {
+            this.buffer_len = this.encoder.finish(&mut this.buffer).map_err(io_error)?;
+            this.finished = true;
+            ready!(this.poll_drain(cx))?;
+        }
+        return this.pin_inner().poll_shutdown(cx)
+    }
+}
+
+impl<W: AsyncWrite + Unpin> Drop for AsyncSlugEncoderWriter<W> {
+    fn drop(&mut self) {
+        wipe(&mut self.buffer);
+    }
+}
+
+/// # AsyncSlugDecoderReader
+///
+/// Reads an encoded string from `R` and returns the decoded bytes.
+///
+/// Invalid input is reported as an `io::Error` of kind `InvalidData` wrapping the `SlugEncodingError` (with the position in the whole stream). The internal buffers are wiped on drop.
+pub struct AsyncSlugDecoderReader<R: AsyncRead + Unpin> {
+    inner: R,
+    decoder: SlugStreamDecoder,
+    input: [u8;BUFFER_SIZE],
+    input_pos: usize,
+    input_len: usize,
+    output: [u8;BUFFER_SIZE],
+    output_pos: usize,
+    output_len: usize,
+    eof: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncSlugDecoderReader<R> {
+    pub(crate) fn new(usage: SlugEncodingUsage, inner: R) -> Result<Self, SlugEncodingError> {
+        return Ok(Self {
+            inner,
+            decoder: SlugStreamDecoder::new(usage)?,
+            input: [0u8;BUFFER_SIZE],
+            input_pos: 0,
+            input_len: 0,
+            output: [0u8;BUFFER_SIZE],
+            output_pos: 0,
+            output_len: 0,
+            eof: false,
+        })
+    }
+    /// Gets a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        return &self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncSlugDecoderReader<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()))
+        }
+
+        loop {
+            if this.output_pos < this.output_len {
+                let len = buf.remaining().min(this.output_len - this.output_pos);
+                buf.put_slice(&this.output[this.output_pos..this.output_pos + len]);
+                wipe(&mut this.output[this.output_pos..this.output_pos + len]);
+                this.output_pos += len;
+                return Poll::Ready(Ok(()))
+            }
+
+            if this.eof {
+                return Poll::Ready(Ok(()))
+            }
+
+            if this.input_pos == this.input_len {
+                let mut input = ReadBuf::new(&mut this.input);
+                ready!(Pin::new(&mut this.inner).poll_read(cx, &mut input))?;
+                let read = input.filled().len();
+
+                if read == 0 {
+                    this.eof = true;
+                    let written = this.decoder.finish(&mut this.output).map_err(io_error)?;
+                    (this.output_pos, this.output_len) = (0, written);
+                    continue;
+                }
+                (this.input_pos, this.input_len) = (0, read);
+            }
+
+            let (consumed, written) = this.decoder.update_partial(&this.input[this.input_pos..this.input_len], &mut this.output).map_err(io_error)?;
+            this.input_pos += consumed;
+            (this.output_pos, this.output_len) = (0, written);
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> Drop for AsyncSlugDecoderReader<R> {
+    fn drop(&mut self) {
+        wipe(&mut self.input);
+        wipe(&mut self.output);
+    }
+}
+
+#[test]
+fn async_stream_roundtrip() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use crate::base58::Base58Alphabet;
+    use crate::SlugEncodings;
+
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let data: Vec<u8> = (0..3000u32).map(|i| (i * 11 + i / 7) as u8).collect();
+    let encodings = [
+        SlugEncodings::Hex,
+        SlugEncodings::Base32,
+        SlugEncodings::Base32unpadded,
+        SlugEncodings::Base64,
+        SlugEncodings::Base64urlsafe,
+    ];
+    let usages = encodings.into_iter().map(SlugEncodingUsage::new)
+        .chain([SlugEncodingUsage::new(SlugEncodings::Base58monero).with_alphabet(Base58Alphabet::RIPPLE)]);
+
+    runtime.block_on(async {
+        for usage in usages {
+
+            for len in [0, 1, 4, 7, 2999, 3000] {
+                // A duplex pipe smaller than the internal buffer, so writes return `Pending`
+                let (client, mut server) = tokio::io::duplex(64);
+                let input = data[..len].to_vec();
+
+                let writer = tokio::spawn(async move {
+                    let mut writer = usage.async_encoder_writer(client).unwrap();
+                    for chunk in input.chunks(13) {
+                        writer.write_all(chunk).await.unwrap();
+                    }
+                    writer.shutdown().await.unwrap();
+                });
+                let mut encoded = Vec::new();
+                server.read_to_end(&mut encoded).await.unwrap();
+                writer.await.unwrap();
+                assert_eq!(encoded, usage.encode(&data[..len]).unwrap().as_bytes());
+
+                let mut reader = usage.async_decoder_reader(encoded.as_slice()).unwrap();
+                let mut decoded = Vec::new();
+                reader.read_to_end(&mut decoded).await.unwrap();
+                assert_eq!(decoded, &data[..len]);
+            }
+        }
+
+        let usage = crate::SlugEncodingUsage::new(SlugEncodings::Base58);
+        assert!(usage.async_encoder_writer(Vec::new()).is_err());
+
+        // Nothing is written before a whole block (or shutdown)
+        let mut writer = crate::SlugEncodingUsage::new(SlugEncodings::Base64).async_encoder_writer(Vec::new()).unwrap();
+        writer.write_all(b"ab").await.unwrap();
+        writer.flush().await.unwrap();
+        assert!(writer.get_ref().is_empty());
+        assert!(writer.into_inner().is_empty());
+
+        let mut reader = crate::SlugEncodingUsage::new(SlugEncodings::Hex).async_decoder_reader(&b"00zz"[..]).unwrap();
+        assert_eq!(reader.read_to_end(&mut Vec::new()).await.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    });
+}
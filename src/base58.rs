@@ -0,0 +1,603 @@
+//! # Base58 (Constant-Time)
+//!
+//! A data-independent Base58 implementation. The radix conversion always runs over the full working buffer, the leading zeros are counted without early exits, and the alphabet is looked up by scanning every symbol with `subtle` instead of indexing a table.
+//!
+//! **Note:** The *length* of a Base58 string depends on the value being encoded (leading zero bytes become leading `1`s), so only the length of the output is revealed, never the digits themselves.
+//!
+//! The working buffer is the output buffer itself, and whatever is left over past the result is wiped.
+//!
+//! The fast (not constant-time) variant is still available through the `bs58` crate as `SlugEncodings::Base58fast`.
+//!
+//! Monero's block-wise Base58 (`SlugEncodings::Base58monero`) is also here: the input is split into 8-byte blocks that are each encoded into exactly 11 characters (a shorter final block into 2, 3, 5, 6, 7, 9 or 10), so its length only depends on the input length and it can be streamed. Encoded strings whose final chunk has an impossible length are rejected with `SlugEncodingError::InvalidLength` and chunks that do not fit their block with `SlugEncodingError::Overflow`.
+//!
+//! The functions take the alphabet as `&[u8;58]`. `Base58Alphabet` is a validated alphabet (58 unique, printable ASCII symbols) with the Bitcoin, Ripple, Flickr and Monero alphabets predefined, and is what `SlugEncodingUsage::with_alphabet` takes.
+
+use core::fmt;
+use core::str::FromStr;
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+use crate::errors::{invalid_character, SlugEncodingError};
+use crate::{wipe, SlugEncodings};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+const ENCODING_ERROR: SlugEncodingError = SlugEncodingError::EncodingError { encoding: SlugEncodings::Base58, source: None };
+
+/// The size of a Monero block (in bytes).
+pub const MONERO_BLOCK_LEN: usize = 8;
+
+/// The size of an encoded Monero block (in characters).
+pub const MONERO_ENCODED_BLOCK_LEN: usize = 11;
+
+/// The number of characters a Monero block of 0 to 8 bytes encodes into.
+const MONERO_BLOCK_SIZES: [usize;9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+
+/// The Bitcoin Base58 alphabet (default).
+pub const BITCOIN_ALPHABET: &[u8;58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The Ripple Base58 alphabet.
+pub const RIPPLE_ALPHABET: &[u8;58] = b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz";
+
+/// The Flickr Base58 alphabet (short URLs).
+pub const FLICKR_ALPHABET: &[u8;58] = b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// # Base58Alphabet
+///
+/// A Base58 alphabet of 58 unique, printable ASCII symbols (in digit order).
+///
+/// ## Example Code
+///
+/// ```rust
+/// use slugencode::base58::Base58Alphabet;
+///
+/// fn main() {
+///     assert_eq!("ripple".parse::<Base58Alphabet>().unwrap(), Base58Alphabet::RIPPLE);
+///
+///     let custom = Base58Alphabet::new(b"ABCDEFGHJKLMNPQRSTUVWXYZ123456789abcdefghijkmnopqrstuvwxyz").unwrap();
+///     assert_eq!(custom.as_str().len(), 58);
+///
+///     assert!(Base58Alphabet::new(b"1123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxy").is_err());
+/// }
+/// ```
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+pub struct Base58Alphabet {
+    symbols: [u8;58],
+}
+
+impl Base58Alphabet {
+    /// Bitcoin (the default)
+    pub const BITCOIN: Base58Alphabet = Base58Alphabet { symbols: *BITCOIN_ALPHABET };
+    /// Ripple (XRP addresses)
+    pub const RIPPLE: Base58Alphabet = Base58Alphabet { symbols: *RIPPLE_ALPHABET };
+    /// Flickr (short URLs)
+    pub const FLICKR: Base58Alphabet = Base58Alphabet { symbols: *FLICKR_ALPHABET };
+    /// Monero (the same symbols as Bitcoin)
+    pub const MONERO: Base58Alphabet = Base58Alphabet { symbols: *BITCOIN_ALPHABET };
+
+    /// The predefined alphabets and their names (Monero is left out as it is the same as Bitcoin)
+    const NAMED: [(&'static str, Base58Alphabet);3] = [("bitcoin", Self::BITCOIN), ("ripple", Self::RIPPLE), ("flickr", Self::FLICKR)];
+
+    /// # New
+    ///
+    /// Validates a user-defined alphabet.
+    ///
+    /// Returns `SlugEncodingError::InvalidAlphabet` with the index of the first symbol that is repeated or not printable ASCII.
+    pub fn new(symbols: &[u8;58]) -> Result<Self, SlugEncodingError> {
+        for (i, c) in symbols.iter().enumerate() {
+            if !c.is_ascii_graphic() || symbols[..i].contains(c) {
+                return Err(SlugEncodingError::InvalidAlphabet { encoding: SlugEncodings::Base58, index: Some(i) })
+            }
+        }
+        return Ok(Self { symbols: *symbols })
+    }
+    /// Validates a user-defined alphabet given as a string (returns `SlugEncodingError::InvalidAlphabet` without an index if it is not 58 bytes long)
+    pub fn from_symbols<T: AsRef<[u8]>>(symbols: T) -> Result<Self, SlugEncodingError> {
+        let symbols = <&[u8;58]>::try_from(symbols.as_ref()).map_err(|_| SlugEncodingError::InvalidAlphabet { encoding: SlugEncodings::Base58, index: None })?;
+        return Self::new(symbols)
+    }
+    /// Gets the symbols (in digit order)
+    pub fn as_bytes(&self) -> &[u8;58] {
+        return &self.symbols
+    }
+    /// Gets the symbols as a string
+    pub fn as_str(&self) -> &str {
+        return core::str::from_utf8(&self.symbols).unwrap_or_default()
+    }
+    /// Gets the name of a predefined alphabet (`bitcoin`, `ripple` or `flickr`)
+    pub fn name(&self) -> Option<&'static str> {
+        return Self::NAMED.iter().find(|(_, alphabet)| alphabet == self).map(|(name, _)| *name)
+    }
+}
+
+impl Default for Base58Alphabet {
+    fn default() -> Self {
+        return Self::BITCOIN
+    }
+}
+
+impl fmt::Debug for Base58Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "Base58Alphabet({})", name),
+            None => write!(f, "Base58Alphabet({:?})", self.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for Base58Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str(self.name().unwrap_or(self.as_str()))
+    }
+}
+
+impl FromStr for Base58Alphabet {
+    type Err = SlugEncodingError;
+
+    /// Parses the name of a predefined alphabet (`bitcoin`, `ripple`, `flickr` or `monero`, case-insensitive) or the 58 symbols of a user-defined one.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("monero") {
+            return Ok(Self::MONERO)
+        }
+        match Self::NAMED.iter().find(|(name, _)| name.eq_ignore_ascii_case(s)) {
+            Some((_, alphabet)) => Ok(*alphabet),
+            None => Self::from_symbols(s),
+        }
+    }
+}
+
+/// # Encoded Length
+///
+/// The maximum length of the Base58 string for `bin_len` bytes. This is also the size of the working buffer needed by `encode_to_slice`.
+pub const fn encoded_len(bin_len: usize) -> usize {
+    bin_len * 138 / 100 + 1
+}
+
+/// # Decoded Length
+///
+/// The maximum length of the bytes decoded from `encoded_len` characters. This is also the size of the working buffer needed by `decode_to_slice`.
+pub const fn decoded_len(encoded_len: usize) -> usize {
+    encoded_len
+}
+
+/// # \[Constant-Time] Encode To Slice
+///
+/// Encodes `bin` into `encoded` using the given alphabet, returning the Base58 string as a sub-slice of `encoded`.
+///
+/// `encoded` must be at least `encoded_len(bin.len())` bytes long as it is used as the working buffer.
+pub fn encode_to_slice<'a>(bin: &[u8], alphabet: &[u8;58], encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    let len = encoded_len(bin.len());
+
+    if encoded.len() < len {
+        return Err(SlugEncodingError::BufferTooSmall { encoding: SlugEncodings::Base58, required: len })
+    }
+
+    let digits = &mut encoded[..len];
+    digits.fill(0);
+
+    // Radix conversion (little-endian digits), always over the whole buffer
+    for &byte in bin {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            let q = div58(carry);
+            *digit = (carry - q * 58) as u8;
+            carry = q;
+        }
+    }
+
+    let zeros = count_leading(bin.iter(), 0);
+    let unused_digits = count_leading(digits.iter().rev(), 0);
+
+    digits.reverse();
+    for digit in digits.iter_mut() {
+        *digit = lookup(alphabet, *digit);
+    }
+
+    let start = unused_digits.checked_sub(zeros).ok_or(ENCODING_ERROR)?;
+    encoded.copy_within(start..len, 0);
+    wipe(&mut encoded[len - start..len]);
+
+    core::str::from_utf8(&encoded[..len - start]).map_err(|_| ENCODING_ERROR)
+}
+
+/// # \[Constant-Time] Decode To Slice
+///
+/// Decodes the Base58 string `encoded` into `bin` using the given alphabet, returning the bytes as a sub-slice of `bin`.
+///
+/// `bin` must be at least `decoded_len(encoded.len())` bytes long as it is used as the working buffer.
+pub fn decode_to_slice<'a>(encoded: &[u8], alphabet: &[u8;58], bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+    let len = decoded_len(encoded.len());
+
+    if bin.len() < len {
+        return Err(SlugEncodingError::BufferTooSmall { encoding: SlugEncodings::Base58, required: len })
+    }
+
+    let bytes = &mut bin[..len];
+    bytes.fill(0);
+
+    let mut invalid = Choice::from(0u8);
+
+    // Radix conversion (little-endian bytes), always over the whole buffer
+    for &c in encoded {
+        let (value, valid) = reverse_lookup(alphabet, c);
+        invalid |= !valid;
+
+        let mut carry = value as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+    }
+
+    let zeros = count_leading(encoded.iter(), alphabet[0]);
+    let unused_bytes = count_leading(bytes.iter().rev(), 0);
+
+    bytes.reverse();
+
+    if bool::from(invalid) {
+        wipe(bytes);
+
+        // Error path only (not constant-time)
+        let index = encoded.iter().position(|c| !alphabet.contains(c)).unwrap_or(0);
+        return Err(invalid_character(SlugEncodings::Base58, encoded, index, None))
+    }
+
+    let start = unused_bytes.checked_sub(zeros).ok_or(SlugEncodingError::DecodingError { encoding: SlugEncodings::Base58, source: None })?;
+    bin.copy_within(start..len, 0);
+    wipe(&mut bin[len - start..len]);
+
+    Ok(&bin[..len - start])
+}
+
+/// # Monero Encoded Length
+///
+/// The exact length of the Monero Base58 string for `bin_len` bytes.
+pub const fn monero_encoded_len(bin_len: usize) -> usize {
+    bin_len / MONERO_BLOCK_LEN * MONERO_ENCODED_BLOCK_LEN + MONERO_BLOCK_SIZES[bin_len % MONERO_BLOCK_LEN]
+}
+
+/// # Monero Decoded Length
+///
+/// The length of the bytes decoded from `encoded_len` characters (exact if `encoded_len` is valid).
+pub const fn monero_decoded_len(encoded_len: usize) -> usize {
+    let rest = encoded_len % MONERO_ENCODED_BLOCK_LEN;
+    let mut last = 0;
+
+    while last < MONERO_BLOCK_LEN && MONERO_BLOCK_SIZES[last + 1] <= rest {
+        last += 1;
+    }
+    encoded_len / MONERO_ENCODED_BLOCK_LEN * MONERO_BLOCK_LEN + last
+}
+
+/// # \[Constant-Time] Encode Monero To Slice
+///
+/// Encodes `bin` into `encoded` a block at a time (Monero addresses and integrated addresses), returning the string as a sub-slice of `encoded`.
+///
+/// `encoded` must be at least `monero_encoded_len(bin.len())` bytes long.
+pub fn encode_monero_to_slice<'a>(bin: &[u8], alphabet: &[u8;58], encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    let len = monero_encoded_len(bin.len());
+
+    if encoded.len() < len {
+        return Err(SlugEncodingError::BufferTooSmall { encoding: SlugEncodings::Base58monero, required: len })
+    }
+
+    for (i, block) in bin.chunks(MONERO_BLOCK_LEN).enumerate() {
+        let start = i * MONERO_ENCODED_BLOCK_LEN;
+        let digits = &mut encoded[start..start + MONERO_BLOCK_SIZES[block.len()]];
+        digits.fill(0);
+
+        // Radix conversion (little-endian digits) into exactly the block's number of characters
+        for &byte in block {
+            let mut carry = byte as u32;
+            for digit in digits.iter_mut() {
+                carry += (*digit as u32) << 8;
+                let q = div58(carry);
+                *digit = (carry - q * 58) as u8;
+                carry = q;
+            }
+        }
+
+        digits.reverse();
+        for digit in digits.iter_mut() {
+            *digit = lookup(alphabet, *digit);
+        }
+    }
+
+    core::str::from_utf8(&encoded[..len]).map_err(|_| SlugEncodingError::EncodingError { encoding: SlugEncodings::Base58monero, source: None })
+}
+
+/// # \[Constant-Time] Decode Monero To Slice
+///
+/// Decodes the Monero Base58 string `encoded` into `bin` a chunk of 11 characters at a time, returning the bytes as a sub-slice of `bin`.
+///
+/// `bin` must be at least `monero_decoded_len(encoded.len())` bytes long.
+///
+/// Returns `SlugEncodingError::InvalidLength` if the final chunk is 1, 4 or 8 characters long (no block encodes into those), and `SlugEncodingError::Overflow` if a chunk is larger than its block can hold (e.g. `5R` is 256 and a 2-character chunk holds 1 byte).
+pub fn decode_monero_to_slice<'a>(encoded: &[u8], alphabet: &[u8;58], bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+    let encoding = SlugEncodings::Base58monero;
+    let len = monero_decoded_len(encoded.len());
+
+    if !MONERO_BLOCK_SIZES.contains(&(encoded.len() % MONERO_ENCODED_BLOCK_LEN)) {
+        return Err(SlugEncodingError::InvalidLength { encoding, length: encoded.len(), source: None })
+    }
+    if bin.len() < len {
+        return Err(SlugEncodingError::BufferTooSmall { encoding, required: len })
+    }
+
+    let mut invalid = Choice::from(0u8);
+    let mut overflow = Choice::from(0u8);
+
+    for (i, chunk) in encoded.chunks(MONERO_ENCODED_BLOCK_LEN).enumerate() {
+        let start = i * MONERO_BLOCK_LEN;
+        let block_len = MONERO_BLOCK_SIZES.iter().position(|&size| size == chunk.len()).unwrap_or(0);
+        let bytes = &mut bin[start..start + block_len];
+        bytes.fill(0);
+
+        // Radix conversion (little-endian bytes), keeping track of any carry out of the block
+        for &c in chunk {
+            let (value, valid) = reverse_lookup(alphabet, c);
+            invalid |= !valid;
+
+            let mut carry = value as u32;
+            for byte in bytes.iter_mut() {
+                carry += (*byte as u32) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            overflow |= !carry.ct_eq(&0);
+        }
+
+        bytes.reverse();
+    }
+
+    if bool::from(invalid) {
+        wipe(&mut bin[..len]);
+
+        // Error path only (not constant-time)
+        let index = encoded.iter().position(|c| !alphabet.contains(c)).unwrap_or(0);
+        return Err(invalid_character(encoding, encoded, index, None))
+    }
+    if bool::from(overflow) {
+        wipe(&mut bin[..len]);
+        return Err(SlugEncodingError::Overflow { encoding })
+    }
+
+    Ok(&bin[..len])
+}
+
+/// # \[Constant-Time] Encode To String
+#[cfg(feature = "alloc")]
+pub fn encode_to_string<T: AsRef<[u8]>>(bin: T, alphabet: &[u8;58]) -> Result<String, SlugEncodingError> {
+    let bin = bin.as_ref();
+    let mut encoded = vec![0u8; encoded_len(bin.len())];
+    let len = encode_to_slice(bin, alphabet, &mut encoded)?.len();
+    encoded.truncate(len);
+
+    String::from_utf8(encoded).map_err(|_| ENCODING_ERROR)
+}
+
+/// # \[Constant-Time] Decode To Vec
+#[cfg(feature = "alloc")]
+pub fn decode_to_vec<T: AsRef<[u8]>>(encoded: T, alphabet: &[u8;58]) -> Result<Vec<u8>, SlugEncodingError> {
+    let encoded = encoded.as_ref();
+    let mut bin = vec![0u8; decoded_len(encoded.len())];
+    let len = decode_to_slice(encoded, alphabet, &mut bin)?.len();
+    bin.truncate(len);
+
+    Ok(bin)
+}
+
+/// # \[Constant-Time] Encode Monero To String
+#[cfg(feature = "alloc")]
+pub fn encode_monero_to_string<T: AsRef<[u8]>>(bin: T, alphabet: &[u8;58]) -> Result<String, SlugEncodingError> {
+    let bin = bin.as_ref();
+    let mut encoded = vec![0u8; monero_encoded_len(bin.len())];
+    encode_monero_to_slice(bin, alphabet, &mut encoded)?;
+
+    String::from_utf8(encoded).map_err(|_| SlugEncodingError::EncodingError { encoding: SlugEncodings::Base58monero, source: None })
+}
+
+/// # \[Constant-Time] Decode Monero To Vec
+#[cfg(feature = "alloc")]
+pub fn decode_monero_to_vec<T: AsRef<[u8]>>(encoded: T, alphabet: &[u8;58]) -> Result<Vec<u8>, SlugEncodingError> {
+    let encoded = encoded.as_ref();
+    let mut bin = vec![0u8; monero_decoded_len(encoded.len())];
+    decode_monero_to_slice(encoded, alphabet, &mut bin)?;
+
+    Ok(bin)
+}
+
+/// Divides `x` by 58 using a multiply-and-shift (valid for `x < 58 * 256`).
+#[inline]
+fn div58(x: u32) -> u32 {
+    (x * 4520) >> 18
+}
+
+/// Counts how many of the leading items equal `value` without exiting early.
+fn count_leading<'a, I: Iterator<Item = &'a u8>>(iter: I, value: u8) -> usize {
+    let mut leading = Choice::from(1u8);
+    let mut count = 0usize;
+
+    for b in iter {
+        leading &= b.ct_eq(&value);
+        count += leading.unwrap_u8() as usize;
+    }
+    count
+}
+
+/// Maps a digit to its symbol by scanning the whole alphabet.
+#[inline]
+fn lookup(alphabet: &[u8;58], digit: u8) -> u8 {
+    let mut symbol = 0u8;
+
+    for (i, &a) in alphabet.iter().enumerate() {
+        symbol.conditional_assign(&a, digit.ct_eq(&(i as u8)));
+    }
+    symbol
+}
+
+/// Maps a symbol to its digit by scanning the whole alphabet.
+#[inline]
+fn reverse_lookup(alphabet: &[u8;58], symbol: u8) -> (u8, Choice) {
+    let mut digit = 0u8;
+    let mut valid = Choice::from(0u8);
+
+    for (i, &a) in alphabet.iter().enumerate() {
+        let matches = symbol.ct_eq(&a);
+        digit.conditional_assign(&(i as u8), matches);
+        valid |= matches;
+    }
+    (digit, valid)
+}
+
+#[test]
+fn div58_exhaustive() {
+    for x in 0..(58 * 256) {
+        assert_eq!(div58(x), x / 58);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn base58_matches_bs58() {
+    let mut inputs: Vec<Vec<u8>> = vec![vec![], vec![0], vec![0, 0, 0], vec![0, 0, 1, 2, 3], vec![255; 64], vec![57]];
+    for len in 1..80usize {
+        inputs.push((0..len).map(|i| (i * 151 + len * 7) as u8).collect());
+    }
+
+    for input in inputs {
+        let encoded = encode_to_string(&input, BITCOIN_ALPHABET).unwrap();
+        assert_eq!(encoded, bs58::encode(&input).into_string());
+        assert_eq!(decode_to_vec(&encoded, BITCOIN_ALPHABET).unwrap(), input);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn base58_rejects_invalid() {
+    let e = decode_to_vec("1FfmbHfnpaZjKFvyi1okTjJJusN455paP0", BITCOIN_ALPHABET).unwrap_err();
+    assert_eq!((e.position(), e.character()), (Some(33), Some('0')));
+    assert!(decode_to_vec("Il", BITCOIN_ALPHABET).is_err());
+    assert!(decode_to_vec("é", BITCOIN_ALPHABET).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn base58_alphabets() {
+    let alphabets = [(Base58Alphabet::BITCOIN, bs58::Alphabet::BITCOIN), (Base58Alphabet::RIPPLE, bs58::Alphabet::RIPPLE), (Base58Alphabet::FLICKR, bs58::Alphabet::FLICKR)];
+
+    for (alphabet, expected) in alphabets {
+        for len in [0usize, 1, 5, 32, 64] {
+            let input: Vec<u8> = (0..len).map(|i| (i * 89 + len) as u8).collect();
+            let encoded = encode_to_string(&input, alphabet.as_bytes()).unwrap();
+
+            assert_eq!(encoded, bs58::encode(&input).with_alphabet(expected).into_string());
+            assert_eq!(decode_to_vec(&encoded, alphabet.as_bytes()).unwrap(), input);
+        }
+        assert_eq!(alphabet.to_string().parse::<Base58Alphabet>(), Ok(alphabet));
+    }
+
+    // The Ripple account zero (`rrrrrrrrrrrrrrrrrrrrrhoLvTp`) is `1111111111111111111114oLvT2` in Bitcoin's alphabet
+    let account_zero = decode_to_vec("1111111111111111111114oLvT2", BITCOIN_ALPHABET).unwrap();
+    assert_eq!(encode_to_string(&account_zero, RIPPLE_ALPHABET).unwrap(), "rrrrrrrrrrrrrrrrrrrrrhoLvTp");
+
+    let invalid = |index| Err(SlugEncodingError::InvalidAlphabet { encoding: SlugEncodings::Base58, index });
+    assert_eq!(Base58Alphabet::from_symbols("123"), invalid(None));
+    assert_eq!(Base58Alphabet::from_symbols("123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxy1"), invalid(Some(57)));
+    assert_eq!(Base58Alphabet::from_symbols("12345678 ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"), invalid(Some(8)));
+    assert_eq!("monero".parse::<Base58Alphabet>().unwrap().name(), Some("bitcoin"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn base58_monero() {
+    // Monero's own block test vectors
+    let vectors: [(&[u8], &str);9] = [
+        (&[], ""),
+        (&[0x00], "11"),
+        (&[0x39], "1z"),
+        (&[0xff], "5Q"),
+        (&[0x00, 0x39], "11z"),
+        (&[0xff, 0xff], "LUv"),
+        (&[0x06, 0x15, 0x60], "133Ww"),
+        (&[0xff; 8], "jpXCZedGfVQ"),
+        (&[0xff; 9], "jpXCZedGfVQ5Q"),
+    ];
+
+    for (bin, encoded) in vectors {
+        assert_eq!(encode_monero_to_string(bin, BITCOIN_ALPHABET).unwrap(), encoded);
+        assert_eq!(decode_monero_to_vec(encoded, BITCOIN_ALPHABET).unwrap(), bin);
+        assert_eq!((monero_encoded_len(bin.len()), monero_decoded_len(encoded.len())), (encoded.len(), bin.len()));
+    }
+
+    // The Monero General Fund address: network byte 18, the public spend and view keys and a 4-byte checksum
+    let address = "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A";
+    let decoded = decode_monero_to_vec(address, BITCOIN_ALPHABET).unwrap();
+    assert_eq!((decoded.len(), decoded[0]), (69, 18));
+    assert_eq!(encode_monero_to_string(&decoded, BITCOIN_ALPHABET).unwrap(), address);
+
+    let invalid_length = |length| Err(SlugEncodingError::InvalidLength { encoding: SlugEncodings::Base58monero, length, source: None });
+    assert_eq!(decode_monero_to_vec("1", BITCOIN_ALPHABET), invalid_length(1));
+    assert_eq!(decode_monero_to_vec("111111111111111", BITCOIN_ALPHABET), invalid_length(15));
+    assert_eq!(decode_monero_to_vec("5R", BITCOIN_ALPHABET), Err(SlugEncodingError::Overflow { encoding: SlugEncodings::Base58monero }));
+    assert_eq!(decode_monero_to_vec("zzzzzzzzzzz", BITCOIN_ALPHABET), Err(SlugEncodingError::Overflow { encoding: SlugEncodings::Base58monero }));
+
+    let e = decode_monero_to_vec("11111111111l1", BITCOIN_ALPHABET).unwrap_err();
+    assert_eq!((e.position(), e.character()), (Some(11), Some('l')));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn base58_monero_usage() {
+    use crate::{SlugDecoder, SlugEncoder, SlugEncodingUsage};
+
+    let address = "44AFFq5kSiGBoZ4NMDwYtN18obc8AemS33DBLWs3H7otXft3XjrpDtQGv7SqSsaBYBb98uNbr2VBBEt7f2wfn3RVGQBEP3A";
+    let decoded: [u8;69] = address.from_base58_monero_array().unwrap();
+    assert_eq!(decoded.to_base58_monero().unwrap(), address);
+
+    // Whole-number Base58 of the same bytes is a different string
+    assert_ne!(decoded.to_base58().unwrap(), address);
+
+    let usage = SlugEncodingUsage::new(SlugEncodings::Base58monero);
+    let mut buf = [0u8;95];
+    assert_eq!(usage.encode_to_slice(&decoded, &mut buf).unwrap(), address);
+    assert_eq!(usage.decode(address).unwrap(), decoded);
+
+    // The length of the last chunk is checked across a stream too
+    let mut decoder = usage.stream_decoder().unwrap();
+    decoder.update(&address[..89]).unwrap();
+    assert_eq!(decoder.finalize(), Err(SlugEncodingError::InvalidLength { encoding: SlugEncodings::Base58monero, length: 89, source: None }));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn base58_alphabet_usage() {
+    use crate::{SlugEncoder, SlugEncodingUsage};
+
+    let bytes = "Hello World!".as_bytes();
+
+    for alphabet in [Base58Alphabet::RIPPLE, Base58Alphabet::FLICKR] {
+        let ct = SlugEncodingUsage::new(SlugEncodings::Base58).with_alphabet(alphabet);
+        let fast = SlugEncodingUsage::new(SlugEncodings::Base58fast).with_alphabet(alphabet);
+
+        let encoded = ct.encode(bytes).unwrap();
+        assert_eq!(encoded, fast.encode(bytes).unwrap());
+        assert_ne!(encoded, bytes.to_base58().unwrap());
+        assert_eq!((ct.decode(&encoded).unwrap(), fast.decode(&encoded).unwrap()), (bytes.to_vec(), bytes.to_vec()));
+        assert_eq!(ct.decode_into::<_, 12>(&encoded).unwrap(), bytes);
+    }
+
+    // `0` is a symbol (zero) of this alphabet and `1` is not
+    let custom = Base58Alphabet::from_symbols("023456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz").unwrap();
+    for encoding in [SlugEncodings::Base58, SlugEncodings::Base58fast] {
+        let usage = SlugEncodingUsage::new(encoding).with_alphabet(custom);
+        assert_eq!(usage.encode([0u8, 0]).unwrap(), "00");
+        assert_eq!(usage.decode("0z").unwrap(), [0u8, 57]);
+
+        let e = usage.decode("0z1").unwrap_err();
+        assert_eq!((e.position(), e.character()), (Some(2), Some('1')));
+    }
+}
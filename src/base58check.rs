@@ -0,0 +1,250 @@
+//! # Base58Check
+//!
+//! Requires the `base58check` feature.
+//!
+//! Base58Check (Bitcoin addresses, WIF private keys, extended keys) encodes version bytes, the payload and a 4-byte checksum (the first bytes of the double SHA-256 of the version and payload) with the constant-time Base58 in `slugencode::base58`.
+//!
+//! - `encode` prepends the version bytes and appends the checksum.
+//! - `decode` verifies the checksum and returns the version bytes followed by the payload, `decode_version` also checks (and strips) the expected version bytes, and `decode_known` identifies them in `VERSIONS`.
+//! - A checksum that does not match is reported as `SlugEncodingError::InvalidChecksum` (it is compared in constant-time) and unexpected version bytes as `SlugEncodingError::InvalidVersion`.
+//!
+//! `SlugEncoder::to_base58check` and `SlugDecoder::from_base58check`/`from_base58check_version` are shortcuts for `encode`, `decode` and `decode_version`.
+//!
+//! ## Example Code
+//!
+//! ```rust
+//! use slugencode::base58check::{self, P2PKH};
+//!
+//! fn main() {
+//!     let address = base58check::encode(P2PKH.prefix, [0u8;20]).unwrap();
+//!     assert_eq!(address, "1111111111111111111114oLvT2");
+//!
+//!     let (version, hash) = base58check::decode_known(&address).unwrap();
+//!     assert_eq!((version.name, hash), ("p2pkh", vec![0u8;20]));
+//! }
+//! ```
+
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::errors::SlugEncodingError;
+use crate::{base58, wipe, SlugEncodings};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// The length of the checksum appended to the version and payload (in bytes).
+pub const CHECKSUM_LEN: usize = 4;
+
+const INVALID_CHECKSUM: SlugEncodingError = SlugEncodingError::InvalidChecksum { encoding: SlugEncodings::Base58, index: None };
+
+/// # Network
+///
+/// The network a version prefix belongs to.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+}
+
+/// # Base58CheckVersion
+///
+/// A well-known version prefix, along with the payload lengths it is used with.
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct Base58CheckVersion {
+    pub name: &'static str,
+    pub network: Network,
+    pub prefix: &'static [u8],
+    pub payload_lens: &'static [usize],
+}
+
+/// Pay-to-pubkey-hash address (`1...`)
+pub const P2PKH: Base58CheckVersion = Base58CheckVersion { name: "p2pkh", network: Network::Mainnet, prefix: &[0x00], payload_lens: &[20] };
+/// Pay-to-script-hash address (`3...`)
+pub const P2SH: Base58CheckVersion = Base58CheckVersion { name: "p2sh", network: Network::Mainnet, prefix: &[0x05], payload_lens: &[20] };
+/// WIF private key (`5...`, or `K...`/`L...` with the compressed flag)
+pub const WIF: Base58CheckVersion = Base58CheckVersion { name: "wif", network: Network::Mainnet, prefix: &[0x80], payload_lens: &[32, 33] };
+/// BIP-32 extended public key (`xpub...`)
+pub const XPUB: Base58CheckVersion = Base58CheckVersion { name: "xpub", network: Network::Mainnet, prefix: &[0x04, 0x88, 0xb2, 0x1e], payload_lens: &[74] };
+/// BIP-32 extended private key (`xprv...`)
+pub const XPRV: Base58CheckVersion = Base58CheckVersion { name: "xprv", network: Network::Mainnet, prefix: &[0x04, 0x88, 0xad, 0xe4], payload_lens: &[74] };
+/// BIP-49 extended public key (`ypub...`)
+pub const YPUB: Base58CheckVersion = Base58CheckVersion { name: "ypub", network: Network::Mainnet, prefix: &[0x04, 0x9d, 0x7c, 0xb2], payload_lens: &[74] };
+/// BIP-49 extended private key (`yprv...`)
+pub const YPRV: Base58CheckVersion = Base58CheckVersion { name: "yprv", network: Network::Mainnet, prefix: &[0x04, 0x9d, 0x78, 0x78], payload_lens: &[74] };
+/// BIP-84 extended public key (`zpub...`)
+pub const ZPUB: Base58CheckVersion = Base58CheckVersion { name: "zpub", network: Network::Mainnet, prefix: &[0x04, 0xb2, 0x47, 0x46], payload_lens: &[74] };
+/// BIP-84 extended private key (`zprv...`)
+pub const ZPRV: Base58CheckVersion = Base58CheckVersion { name: "zprv", network: Network::Mainnet, prefix: &[0x04, 0xb2, 0x43, 0x0c], payload_lens: &[74] };
+/// Testnet pay-to-pubkey-hash address (`m...`/`n...`)
+pub const TESTNET_P2PKH: Base58CheckVersion = Base58CheckVersion { name: "testnet-p2pkh", network: Network::Testnet, prefix: &[0x6f], payload_lens: &[20] };
+/// Testnet pay-to-script-hash address (`2...`)
+pub const TESTNET_P2SH: Base58CheckVersion = Base58CheckVersion { name: "testnet-p2sh", network: Network::Testnet, prefix: &[0xc4], payload_lens: &[20] };
+/// Testnet WIF private key (`9...`, or `c...` with the compressed flag)
+pub const TESTNET_WIF: Base58CheckVersion = Base58CheckVersion { name: "testnet-wif", network: Network::Testnet, prefix: &[0xef], payload_lens: &[32, 33] };
+/// Testnet extended public key (`tpub...`)
+pub const TPUB: Base58CheckVersion = Base58CheckVersion { name: "tpub", network: Network::Testnet, prefix: &[0x04, 0x35, 0x87, 0xcf], payload_lens: &[74] };
+/// Testnet extended private key (`tprv...`)
+pub const TPRV: Base58CheckVersion = Base58CheckVersion { name: "tprv", network: Network::Testnet, prefix: &[0x04, 0x35, 0x83, 0x94], payload_lens: &[74] };
+
+/// Every well-known version prefix (used by `Base58CheckVersion::identify`).
+pub const VERSIONS: &[Base58CheckVersion] = &[P2PKH, P2SH, WIF, XPUB, XPRV, YPUB, YPRV, ZPUB, ZPRV, TESTNET_P2PKH, TESTNET_P2SH, TESTNET_WIF, TPUB, TPRV];
+
+impl Base58CheckVersion {
+    /// Finds the well-known version of `data` (the version bytes followed by the payload) from its prefix and payload length
+    pub fn identify(data: &[u8]) -> Option<&'static Base58CheckVersion> {
+        return VERSIONS.iter().find(|version| data.starts_with(version.prefix) && version.payload_lens.contains(&(data.len() - version.prefix.len())))
+    }
+    /// Finds a well-known version by name (e.g. `xpub`)
+    pub fn from_name(name: &str) -> Option<&'static Base58CheckVersion> {
+        return VERSIONS.iter().find(|version| version.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// # Checksum
+///
+/// Returns the first `CHECKSUM_LEN` bytes of the double SHA-256 of `bytes`.
+pub fn checksum<T: AsRef<[u8]>>(bytes: T) -> [u8;CHECKSUM_LEN] {
+    let digest = Sha256::digest(Sha256::digest(bytes.as_ref()));
+
+    let mut checksum = [0u8;CHECKSUM_LEN];
+    checksum.copy_from_slice(&digest[..CHECKSUM_LEN]);
+    return checksum
+}
+
+/// # \[Constant-Time] Verify Checksum
+///
+/// Splits the checksum off the end of `checked` and verifies it, returning the version bytes and payload.
+///
+/// Returns `SlugEncodingError::InvalidChecksum` if the checksum does not match (or `checked` is shorter than the checksum).
+pub fn verify_checksum(checked: &[u8]) -> Result<&[u8], SlugEncodingError> {
+    let data_len = checked.len().checked_sub(CHECKSUM_LEN).ok_or(INVALID_CHECKSUM)?;
+    let (data, expected) = checked.split_at(data_len);
+
+    if !bool::from(checksum(data).ct_eq(expected)) {
+        return Err(INVALID_CHECKSUM)
+    }
+    return Ok(data)
+}
+
+/// # \[Constant-Time] Decode To Slice
+///
+/// Decodes `encoded` into `bin` (see `base58::decode_to_slice`) and verifies the checksum, returning the version bytes and payload as a sub-slice of `bin`.
+pub fn decode_to_slice<'a>(encoded: &[u8], bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+    let checked_len = base58::decode_to_slice(encoded, base58::BITCOIN_ALPHABET, bin)?.len();
+    let data_len = match verify_checksum(&bin[..checked_len]) {
+        Ok(data) => data.len(),
+        Err(e) => {
+            wipe(&mut bin[..checked_len]);
+            return Err(e)
+        }
+    };
+
+    wipe(&mut bin[data_len..checked_len]);
+    return Ok(&bin[..data_len])
+}
+
+/// # Encode
+///
+/// Encodes `version` followed by `payload` and the checksum.
+#[cfg(feature = "alloc")]
+pub fn encode<V: AsRef<[u8]>, T: AsRef<[u8]>>(version: V, payload: T) -> Result<String, SlugEncodingError> {
+    let (version, payload) = (version.as_ref(), payload.as_ref());
+
+    let mut checked = Vec::with_capacity(version.len() + payload.len() + CHECKSUM_LEN);
+    checked.extend_from_slice(version);
+    checked.extend_from_slice(payload);
+    checked.extend_from_slice(&checksum(&checked));
+
+    let encoded = base58::encode_to_string(&checked, base58::BITCOIN_ALPHABET);
+    wipe(&mut checked);
+    return encoded
+}
+
+/// # Decode
+///
+/// Verifies the checksum of `encoded` and returns the version bytes followed by the payload.
+#[cfg(feature = "alloc")]
+pub fn decode<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, SlugEncodingError> {
+    let encoded = encoded.as_ref();
+    let mut bin = alloc::vec![0u8; base58::decoded_len(encoded.len())];
+
+    let len = decode_to_slice(encoded, &mut bin)?.len();
+    bin.truncate(len);
+    return Ok(bin)
+}
+
+/// # Decode (Version)
+///
+/// Verifies the checksum of `encoded` and that it starts with `version`, returning the payload.
+///
+/// Returns `SlugEncodingError::InvalidVersion` if the version bytes are not `version`.
+#[cfg(feature = "alloc")]
+pub fn decode_version<T: AsRef<[u8]>, V: AsRef<[u8]>>(encoded: T, version: V) -> Result<Vec<u8>, SlugEncodingError> {
+    let version = version.as_ref();
+    let mut data = decode(encoded)?;
+
+    if !data.starts_with(version) {
+        wipe(&mut data);
+        return Err(SlugEncodingError::InvalidVersion { encoding: SlugEncodings::Base58 })
+    }
+    let payload = data[version.len()..].to_vec();
+    wipe(&mut data);
+    return Ok(payload)
+}
+
+/// # Decode (Known Version)
+///
+/// Verifies the checksum of `encoded` and identifies its version in `VERSIONS`, returning the version and the payload.
+///
+/// Returns `SlugEncodingError::InvalidVersion` if the version bytes (with that payload length) are not well-known.
+#[cfg(feature = "alloc")]
+pub fn decode_known<T: AsRef<[u8]>>(encoded: T) -> Result<(&'static Base58CheckVersion, Vec<u8>), SlugEncodingError> {
+    let mut data = decode(encoded)?;
+
+    let version = match Base58CheckVersion::identify(&data) {
+        Some(version) => version,
+        None => {
+            wipe(&mut data);
+            return Err(SlugEncodingError::InvalidVersion { encoding: SlugEncodings::Base58 })
+        }
+    };
+
+    let payload = data[version.prefix.len()..].to_vec();
+    wipe(&mut data);
+    return Ok((version, payload))
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn base58check_vectors() {
+    // Bitcoin wiki (P2PKH address of a known public key hash)
+    let hash = [0x01, 0x09, 0x66, 0x77, 0x60, 0x06, 0x95, 0x3d, 0x55, 0x67, 0x43, 0x9e, 0x5e, 0x39, 0xf8, 0x6a, 0x0d, 0x27, 0x3b, 0xee];
+    assert_eq!(encode(P2PKH.prefix, hash).unwrap(), "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM");
+    assert_eq!(decode_version("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM", [0x00]).unwrap(), hash);
+
+    // BIP-32 test vector 1 (master key)
+    let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+    let (version, payload) = decode_known(xpub).unwrap();
+    assert_eq!((version, payload.len()), (&XPUB, 74));
+    assert_eq!(encode(version.prefix, &payload).unwrap(), xpub);
+
+    let (version, key) = decode_known("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ").unwrap();
+    assert_eq!((version.name, version.network, key[0]), ("wif", Network::Mainnet, 0x0c));
+
+    assert_eq!(Base58CheckVersion::from_name("TPUB"), Some(&TPUB));
+    assert_eq!(Base58CheckVersion::identify(&[0x6f; 21]), Some(&TESTNET_P2PKH));
+    assert_eq!(Base58CheckVersion::identify(&[0x6f; 22]), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn base58check_errors() {
+    // Last character changed
+    assert_eq!(decode("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN"), Err(INVALID_CHECKSUM));
+    assert_eq!(decode("1"), Err(INVALID_CHECKSUM));
+    assert!(matches!(decode("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjv0"), Err(SlugEncodingError::InvalidCharacter { index: 32, .. })));
+
+    assert_eq!(decode_version("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM", [0x05]), Err(SlugEncodingError::InvalidVersion { encoding: SlugEncodings::Base58 }));
+    assert_eq!(decode_known(encode([0x42], [0u8;20]).unwrap()), Err(SlugEncodingError::InvalidVersion { encoding: SlugEncodings::Base58 }));
+}
@@ -0,0 +1,423 @@
+//! # Base85 (Not Constant-Time)
+//!
+//! The Base85 family encodes every 4 bytes as 5 characters. Three variants are provided:
+//!
+//! - **Ascii85** (Adobe): Used in PDF/PostScript. Wrapped in `<~` and `~>` with `z` as a shortcut for an all-zero group.
+//! - **Z85** (ZeroMQ): Used for CURVE keys. The input must be a multiple of 4 bytes.
+//! - **RFC 1924**: Uses the RFC 1924 alphabet with the same chunking as `git` and Python's `base64.b85encode`.
+//!
+//! Ascii85 and RFC 1924 allow a final partial group of `n` bytes, which is encoded as `n + 1` characters.
+//!
+//! Every function has a `_to_slice` variant that writes into a caller-provided buffer. The `String`/`Vec` variants size their buffer up front so it never reallocates (and leaves copies behind).
+
+use crate::errors::{invalid_character, SlugEncodingError};
+use crate::{wipe, SlugEncodings};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+/// The Z85 (ZeroMQ) alphabet.
+pub const Z85_ALPHABET: &[u8;85] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// The RFC 1924 alphabet.
+pub const RFC1924_ALPHABET: &[u8;85] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// The Ascii85 alphabet (`!` to `u`, without the `z` shortcut).
+pub const ASCII85_ALPHABET: &[u8;85] = b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu";
+
+/// The Ascii85 prefix.
+pub const ASCII85_PREFIX: &str = "<~";
+
+/// The Ascii85 suffix.
+pub const ASCII85_SUFFIX: &str = "~>";
+
+/// # Encoded Length
+///
+/// The length of the Z85 or RFC 1924 string for `bin_len` bytes.
+pub const fn encoded_len(bin_len: usize) -> usize {
+    bin_len / 4 * 5 + match bin_len % 4 {
+        0 => 0,
+        partial => partial + 1,
+    }
+}
+
+/// # Decoded Length
+///
+/// The length of the bytes decoded from `encoded_len` Z85 or RFC 1924 characters.
+pub const fn decoded_len(encoded_len: usize) -> usize {
+    encoded_len / 5 * 4 + match encoded_len % 5 {
+        0 => 0,
+        partial => partial - 1,
+    }
+}
+
+/// # Ascii85 Encoded Length
+///
+/// The maximum length of the Ascii85 string (with delimiters) for `bin_len` bytes. Zero groups are shorter.
+pub const fn ascii85_encoded_len(bin_len: usize) -> usize {
+    ASCII85_PREFIX.len() + encoded_len(bin_len) + ASCII85_SUFFIX.len()
+}
+
+/// # Ascii85 Decoded Length
+///
+/// The maximum length of the bytes decoded from `encoded_len` Ascii85 characters (every `z` decodes to 4 bytes).
+pub const fn ascii85_decoded_len(encoded_len: usize) -> usize {
+    encoded_len * 4
+}
+
+/// # To Ascii85
+///
+/// Encodes bytes to Ascii85 with the `<~` and `~>` delimiters.
+#[cfg(feature = "alloc")]
+pub fn encode_ascii85<T: AsRef<[u8]>>(bin: T) -> String {
+    let bin = bin.as_ref();
+    let mut encoded = vec![0u8; ascii85_len(bin)];
+    let len = encode_ascii85_groups(bin, &mut encoded);
+    into_string(encoded, len)
+}
+
+/// # To Ascii85 (Slice)
+///
+/// Encodes bytes to Ascii85 into `encoded`, returning the string as a sub-slice of it.
+pub fn encode_ascii85_to_slice<'a>(bin: &[u8], encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    let required = ascii85_len(bin);
+
+    if encoded.len() < required {
+        return Err(SlugEncodingError::BufferTooSmall { encoding: SlugEncodings::Ascii85, required })
+    }
+
+    let len = encode_ascii85_groups(bin, encoded);
+    as_str(&encoded[..len], SlugEncodings::Ascii85)
+}
+
+/// The exact length of the Ascii85 string for `bin` (zero groups become `z`).
+fn ascii85_len(bin: &[u8]) -> usize {
+    let groups: usize = bin.chunks(4).map(|chunk| if chunk == [0u8;4] { 1 } else { chunk.len() + 1 }).sum();
+    ASCII85_PREFIX.len() + groups + ASCII85_SUFFIX.len()
+}
+
+fn encode_ascii85_groups(bin: &[u8], encoded: &mut [u8]) -> usize {
+    let mut pos = ASCII85_PREFIX.len();
+    encoded[..pos].copy_from_slice(ASCII85_PREFIX.as_bytes());
+
+    for chunk in bin.chunks(4) {
+        if chunk == [0u8;4] {
+            encoded[pos] = b'z';
+            pos += 1;
+            continue
+        }
+        for digit in &encode_group(chunk)[..chunk.len() + 1] {
+            encoded[pos] = digit + b'!';
+            pos += 1;
+        }
+    }
+
+    encoded[pos..pos + ASCII85_SUFFIX.len()].copy_from_slice(ASCII85_SUFFIX.as_bytes());
+    pos + ASCII85_SUFFIX.len()
+}
+
+/// # From Ascii85
+///
+/// Decodes Ascii85 with or without the `<~` and `~>` delimiters. Whitespace is ignored.
+#[cfg(feature = "alloc")]
+pub fn decode_ascii85<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, SlugEncodingError> {
+    let encoded = encoded.as_ref();
+    let (start, end) = ascii85_bounds(encoded)?;
+
+    let mut bin = vec![0u8; ascii85_decoded_size(&encoded[start..end])];
+    let len = decode_ascii85_to_slice(encoded, &mut bin)?.len();
+    bin.truncate(len);
+    Ok(bin)
+}
+
+/// # From Ascii85 (Slice)
+///
+/// Decodes Ascii85 into `bin`, returning the bytes as a sub-slice of it. `bin` only needs room for the bytes the input actually decodes to.
+pub fn decode_ascii85_to_slice<'a>(encoded: &[u8], bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+    let (start, end) = ascii85_bounds(encoded)?;
+    let required = ascii85_decoded_size(&encoded[start..end]);
+
+    if bin.len() < required {
+        return Err(SlugEncodingError::BufferTooSmall { encoding: SlugEncodings::Ascii85, required })
+    }
+
+    let mut group = [0u8;5];
+    let result = decode_ascii85_groups(encoded, start, end, &mut group, bin);
+    wipe(&mut group);
+    finish(result, bin, required)
+}
+
+/// Returns the range between the `<~` and `~>` delimiters (or the whole input without them).
+fn ascii85_bounds(input: &[u8]) -> Result<(usize, usize), SlugEncodingError> {
+    if !input.starts_with(ASCII85_PREFIX.as_bytes()) {
+        return Ok((0, input.len()))
+    }
+    if !input[ASCII85_PREFIX.len()..].ends_with(ASCII85_SUFFIX.as_bytes()) {
+        return Err(SlugEncodingError::InvalidPadding { encoding: SlugEncodings::Ascii85, source: None })
+    }
+    Ok((ASCII85_PREFIX.len(), input.len() - ASCII85_SUFFIX.len()))
+}
+
+/// The number of bytes an Ascii85 body decodes to (assuming it is valid).
+fn ascii85_decoded_size(body: &[u8]) -> usize {
+    let zero_groups = body.iter().filter(|&&c| c == b'z').count();
+    let digits = body.iter().filter(|&&c| c != b'z' && !c.is_ascii_whitespace()).count();
+    zero_groups * 4 + decoded_len(digits)
+}
+
+fn decode_ascii85_groups(input: &[u8], start: usize, end: usize, group: &mut [u8;5], bin: &mut [u8]) -> Result<usize, SlugEncodingError> {
+    let mut len = 0usize;
+    let mut pos = 0usize;
+
+    for (index, &c) in input.iter().enumerate().take(end).skip(start) {
+        match c {
+            b'z' if len == 0 => {
+                bin[pos..pos + 4].fill(0);
+                pos += 4;
+            }
+            b'!'..=b'u' => {
+                group[len] = c - b'!';
+                len += 1;
+
+                if len == 5 {
+                    bin[pos..pos + 4].copy_from_slice(&decode_group(group, SlugEncodings::Ascii85)?);
+                    pos += 4;
+                    len = 0;
+                }
+            }
+            c if c.is_ascii_whitespace() => {}
+            _ => return Err(invalid_character(SlugEncodings::Ascii85, input, index, None)),
+        }
+    }
+
+    decode_partial_group(group, len, &mut bin[pos..], input.len(), SlugEncodings::Ascii85).map(|partial| pos + partial)
+}
+
+/// # To Z85
+///
+/// Encodes bytes to Z85. Fails if the input is not a multiple of 4 bytes.
+#[cfg(feature = "alloc")]
+pub fn encode_z85<T: AsRef<[u8]>>(bin: T) -> Result<String, SlugEncodingError> {
+    let bin = bin.as_ref();
+    let mut encoded = vec![0u8; encoded_len(bin.len())];
+    let len = encode_z85_to_slice(bin, &mut encoded)?.len();
+    Ok(into_string(encoded, len))
+}
+
+/// # To Z85 (Slice)
+pub fn encode_z85_to_slice<'a>(bin: &[u8], encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    if !bin.len().is_multiple_of(4) {
+        return Err(SlugEncodingError::InvalidLength { encoding: SlugEncodings::Z85, length: bin.len(), source: None })
+    }
+    encode_with_alphabet(bin, Z85_ALPHABET, SlugEncodings::Z85, encoded)
+}
+
+/// # From Z85
+///
+/// Decodes Z85. Fails if the input is not a multiple of 5 characters.
+#[cfg(feature = "alloc")]
+pub fn decode_z85<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, SlugEncodingError> {
+    let encoded = encoded.as_ref();
+    let mut bin = vec![0u8; decoded_len(encoded.len())];
+    let len = decode_z85_to_slice(encoded, &mut bin)?.len();
+    bin.truncate(len);
+    Ok(bin)
+}
+
+/// # From Z85 (Slice)
+pub fn decode_z85_to_slice<'a>(encoded: &[u8], bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+    if !encoded.len().is_multiple_of(5) {
+        return Err(SlugEncodingError::InvalidLength { encoding: SlugEncodings::Z85, length: encoded.len(), source: None })
+    }
+    decode_with_alphabet(encoded, Z85_ALPHABET, SlugEncodings::Z85, bin)
+}
+
+/// # To Base85 (RFC 1924)
+#[cfg(feature = "alloc")]
+pub fn encode_rfc1924<T: AsRef<[u8]>>(bin: T) -> String {
+    let bin = bin.as_ref();
+    let mut encoded = vec![0u8; encoded_len(bin.len())];
+    let len = encode_alphabet_groups(bin, RFC1924_ALPHABET, &mut encoded);
+    into_string(encoded, len)
+}
+
+/// # To Base85 (RFC 1924) (Slice)
+pub fn encode_rfc1924_to_slice<'a>(bin: &[u8], encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    encode_with_alphabet(bin, RFC1924_ALPHABET, SlugEncodings::Base85rfc1924, encoded)
+}
+
+/// # From Base85 (RFC 1924)
+#[cfg(feature = "alloc")]
+pub fn decode_rfc1924<T: AsRef<[u8]>>(encoded: T) -> Result<Vec<u8>, SlugEncodingError> {
+    let encoded = encoded.as_ref();
+    let mut bin = vec![0u8; decoded_len(encoded.len())];
+    let len = decode_rfc1924_to_slice(encoded, &mut bin)?.len();
+    bin.truncate(len);
+    Ok(bin)
+}
+
+/// # From Base85 (RFC 1924) (Slice)
+pub fn decode_rfc1924_to_slice<'a>(encoded: &[u8], bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+    decode_with_alphabet(encoded, RFC1924_ALPHABET, SlugEncodings::Base85rfc1924, bin)
+}
+
+fn encode_with_alphabet<'a>(bin: &[u8], alphabet: &[u8;85], encoding: SlugEncodings, encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    let required = encoded_len(bin.len());
+
+    if encoded.len() < required {
+        return Err(SlugEncodingError::BufferTooSmall { encoding, required })
+    }
+
+    let len = encode_alphabet_groups(bin, alphabet, encoded);
+    as_str(&encoded[..len], encoding)
+}
+
+fn encode_alphabet_groups(bin: &[u8], alphabet: &[u8;85], encoded: &mut [u8]) -> usize {
+    let mut pos = 0usize;
+
+    for chunk in bin.chunks(4) {
+        for &digit in &encode_group(chunk)[..chunk.len() + 1] {
+            encoded[pos] = alphabet[digit as usize];
+            pos += 1;
+        }
+    }
+    pos
+}
+
+fn decode_with_alphabet<'a>(encoded: &[u8], alphabet: &[u8;85], encoding: SlugEncodings, bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+    let required = decoded_len(encoded.len());
+
+    if bin.len() < required {
+        return Err(SlugEncodingError::BufferTooSmall { encoding, required })
+    }
+
+    let mut group = [0u8;5];
+    let result = decode_alphabet_groups(encoded, alphabet, encoding, &mut group, bin);
+    wipe(&mut group);
+    finish(result, bin, required)
+}
+
+fn decode_alphabet_groups(encoded: &[u8], alphabet: &[u8;85], encoding: SlugEncodings, group: &mut [u8;5], bin: &mut [u8]) -> Result<usize, SlugEncodingError> {
+    let mut len = 0usize;
+    let mut pos = 0usize;
+
+    for (index, &c) in encoded.iter().enumerate() {
+        group[len] = match alphabet.iter().position(|&a| a == c) {
+            Some(digit) => digit as u8,
+            None => return Err(invalid_character(encoding, encoded, index, None)),
+        };
+        len += 1;
+
+        if len == 5 {
+            bin[pos..pos + 4].copy_from_slice(&decode_group(group, encoding)?);
+            pos += 4;
+            len = 0;
+        }
+    }
+
+    decode_partial_group(group, len, &mut bin[pos..], encoded.len(), encoding).map(|partial| pos + partial)
+}
+
+/// Returns the decoded bytes, or wipes whatever was written before the error.
+fn finish(result: Result<usize, SlugEncodingError>, bin: &mut [u8], written: usize) -> Result<&[u8], SlugEncodingError> {
+    match result {
+        Ok(len) => Ok(&bin[..len]),
+        Err(e) => {
+            wipe(&mut bin[..written]);
+            Err(e)
+        }
+    }
+}
+
+fn as_str(encoded: &[u8], encoding: SlugEncodings) -> Result<&str, SlugEncodingError> {
+    core::str::from_utf8(encoded).map_err(|_| SlugEncodingError::EncodingError { encoding, source: None })
+}
+
+/// Moves the encoded bytes into a `String` without copying (Base85 output is always ASCII).
+#[cfg(feature = "alloc")]
+fn into_string(mut encoded: Vec<u8>, len: usize) -> String {
+    encoded.truncate(len);
+    String::from_utf8(encoded).unwrap_or_default()
+}
+
+/// Encodes up to 4 bytes (zero-padded) into 5 base85 digits.
+fn encode_group(chunk: &[u8]) -> [u8;5] {
+    let mut padded = [0u8;4];
+    padded[..chunk.len()].copy_from_slice(chunk);
+
+    let mut value = u32::from_be_bytes(padded);
+    let mut digits = [0u8;5];
+    wipe(&mut padded);
+
+    for digit in digits.iter_mut().rev() {
+        *digit = (value % 85) as u8;
+        value /= 85;
+    }
+    digits
+}
+
+/// Decodes 5 base85 digits into 4 bytes, rejecting values above `u32::MAX`.
+fn decode_group(digits: &[u8;5], encoding: SlugEncodings) -> Result<[u8;4], SlugEncodingError> {
+    let mut value = 0u64;
+
+    for &digit in digits {
+        value = value * 85 + digit as u64;
+    }
+
+    let value = u32::try_from(value).map_err(|_| SlugEncodingError::Overflow { encoding })?;
+    Ok(value.to_be_bytes())
+}
+
+/// Decodes a final group of `len` digits (padded with the highest digit) into `len - 1` bytes, returning how many were written.
+fn decode_partial_group(group: &mut [u8;5], len: usize, bin: &mut [u8], input_len: usize, encoding: SlugEncodings) -> Result<usize, SlugEncodingError> {
+    match len {
+        0 => Ok(0),
+        1 => Err(SlugEncodingError::InvalidLength { encoding, length: input_len, source: None }),
+        _ => {
+            group[len..].fill(84);
+            bin[..len - 1].copy_from_slice(&decode_group(group, encoding)?[..len - 1]);
+            Ok(len - 1)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn ascii85_vectors() {
+    assert_eq!(encode_ascii85(b"Man is"), "<~9jqo^Bla~>");
+    assert_eq!(encode_ascii85([0u8;4]), "<~z~>");
+    assert_eq!(encode_ascii85(b""), "<~~>");
+    assert_eq!(decode_ascii85("<~9jqo^Bla~>").unwrap(), b"Man is");
+    assert_eq!(decode_ascii85("9jqo^\nBla").unwrap(), b"Man is");
+    assert_eq!(decode_ascii85("<~z9jqo^~>").unwrap(), b"\0\0\0\0Man ");
+    assert!(decode_ascii85("<~9jqo^").is_err());
+    assert_eq!(decode_ascii85("<~9jzqo^~>").unwrap_err().position(), Some(4));
+    assert_eq!(decode_ascii85("<~s8W-\"~>"), Err(SlugEncodingError::Overflow { encoding: SlugEncodings::Ascii85 }));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn z85_vectors() {
+    let bytes = [0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B];
+    assert_eq!(encode_z85(bytes).unwrap(), "HelloWorld");
+    assert_eq!(decode_z85("HelloWorld").unwrap(), bytes);
+
+    let mut encoded = [0u8;10];
+    assert_eq!(encode_z85_to_slice(&bytes, &mut encoded).unwrap(), "HelloWorld");
+    let mut bin = [0u8;8];
+    assert_eq!(decode_z85_to_slice(b"HelloWorld", &mut bin).unwrap(), bytes);
+    assert!(matches!(decode_z85_to_slice(b"HelloWorld", &mut bin[..7]), Err(SlugEncodingError::BufferTooSmall { required: 8, .. })));
+    assert!(encode_z85([1u8;3]).is_err());
+    assert!(decode_z85("Hello~").is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn rfc1924_roundtrip() {
+    assert_eq!(encode_rfc1924(b"hello"), "Xk~0{Zv");
+    for len in 0..20usize {
+        let bytes: Vec<u8> = (0..len).map(|i| (i * 37 + 200) as u8).collect();
+        assert_eq!(decode_rfc1924(encode_rfc1924(&bytes)).unwrap(), bytes);
+        assert_eq!(decode_ascii85(encode_ascii85(&bytes)).unwrap(), bytes);
+    }
+}
@@ -0,0 +1,565 @@
+//! # Bech32 and Bech32m (BIP-173, BIP-350)
+//!
+//! A Bech32 string is a human-readable prefix (HRP, e.g. `bc`, `npub` or `age`), the separator `1`, the data regrouped into 5-bit values (one character of `CHARSET` each) and a 6-character BCH checksum. Bech32m (used by segwit v1+ addresses) only differs in the checksum constant.
+//!
+//! - `encode`/`decode` regroup bytes into 5-bit values (padding the last one with zero bits) and back, which is what Nostr keys and age recipients use.
+//! - `encode_fives`/`decode_fives` work with the 5-bit values directly (segwit addresses start with a witness version that is not regrouped, see `bytes_to_fives`/`fives_to_bytes`).
+//! - `encode_to_slice`/`decode_to_slice` do the same without allocating.
+//!
+//! Strings are limited to `MAX_LENGTH` characters, may be all lowercase or all uppercase (never mixed) and are encoded in the case of the HRP. A checksum that does not match is reported as `SlugEncodingError::InvalidChecksum` along with the position of the wrong character when a single substitution explains it.
+//!
+//! `SlugEncodings::Bech32` and `SlugEncodings::Bech32m` use these through `SlugEncodingUsage`, which needs an HRP to encode (`with_hrp`) and only keeps the data when decoding.
+//!
+//! **Note:** Bech32 is not constant-time.
+//!
+//! ## Example Code
+//!
+//! ```rust
+//! use slugencode::bech32::{self, Bech32Variant};
+//!
+//! # #[cfg(feature = "alloc")]
+//! fn main() {
+//!     let encoded = bech32::encode("age", [0xde, 0xad, 0xbe, 0xef], Bech32Variant::Bech32).unwrap();
+//!     let (hrp, data) = bech32::decode(&encoded, Bech32Variant::Bech32).unwrap();
+//!
+//!     assert_eq!(hrp, "age");
+//!     assert_eq!(data, [0xde, 0xad, 0xbe, 0xef]);
+//! }
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
+//! ```
+
+use core::fmt;
+
+use crate::errors::{invalid_character, SlugEncodingError};
+use crate::SlugEncodings;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+/// The Bech32 alphabet (in digit order).
+pub const CHARSET: &[u8;32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The maximum length of a Bech32 string.
+pub const MAX_LENGTH: usize = 90;
+
+/// The maximum length of the human-readable prefix.
+pub const MAX_HRP_LENGTH: usize = 83;
+
+/// The length of the checksum (in characters).
+pub const CHECKSUM_LENGTH: usize = 6;
+
+const GENERATOR: [u32;5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+/// # Bech32Variant
+///
+/// The checksum constant: Bech32 (BIP-173) or Bech32m (BIP-350).
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Hash)]
+pub enum Bech32Variant {
+    Bech32,
+    Bech32m,
+}
+
+impl Bech32Variant {
+    /// Gets the matching `SlugEncodings` variant
+    pub fn encoding(&self) -> SlugEncodings {
+        match self {
+            Bech32Variant::Bech32 => SlugEncodings::Bech32,
+            Bech32Variant::Bech32m => SlugEncodings::Bech32m,
+        }
+    }
+    fn constant(&self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+/// # Encoded Length
+///
+/// The length of the Bech32 string for `bin_len` bytes with an HRP of `hrp_len` characters.
+pub const fn encoded_len(hrp_len: usize, bin_len: usize) -> usize {
+    hrp_len + 1 + (bin_len * 8).div_ceil(5) + CHECKSUM_LENGTH
+}
+
+/// # Decoded Length
+///
+/// The maximum length of the bytes decoded from `encoded_len` characters (for the shortest HRP).
+pub const fn decoded_len(encoded_len: usize) -> usize {
+    encoded_len.saturating_sub(2 + CHECKSUM_LENGTH) * 5 / 8
+}
+
+/// # Encode To Slice
+///
+/// Regroups `bin` into 5-bit values and encodes them with `hrp` into `encoded`, returning the string as a sub-slice of `encoded`.
+///
+/// Returns `SlugEncodingError::InvalidHrp` for an invalid HRP, `InvalidLength` if the string would be longer than `MAX_LENGTH` and `BufferTooSmall` if `encoded` is shorter than `encoded_len(hrp.len(), bin.len())`.
+pub fn encode_to_slice<'a>(hrp: &str, bin: &[u8], variant: Bech32Variant, encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    return encode_values(hrp, Fives::new(bin), (bin.len() * 8).div_ceil(5), variant, encoded)
+}
+
+/// # Encode Fives To Slice
+///
+/// Encodes 5-bit values (each below 32) with `hrp` into `encoded` (see `encode_to_slice`).
+pub fn encode_fives_to_slice<'a>(hrp: &str, fives: &[u8], variant: Bech32Variant, encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    if fives.iter().any(|&v| v >= 32) {
+        return Err(SlugEncodingError::EncodingError { encoding: variant.encoding(), source: None })
+    }
+    return encode_values(hrp, fives.iter().copied(), fives.len(), variant, encoded)
+}
+
+/// # Decode To Slice
+///
+/// Verifies `encoded` and decodes its data into `bin`, returning the HRP (as written) and the bytes as a sub-slice of `bin`.
+///
+/// Returns `SlugEncodingError::InvalidChecksum` if the checksum does not match, `InvalidPadding` if the last 5-bit value has non-zero padding bits and `BufferTooSmall` if `bin` is shorter than the decoded data.
+pub fn decode_to_slice<'a, 'b>(encoded: &'a [u8], variant: Bech32Variant, bin: &'b mut [u8]) -> Result<(&'a str, &'b [u8]), SlugEncodingError> {
+    let encoding = variant.encoding();
+    let (hrp, data) = parse(encoded, variant)?;
+    let values = &data[..data.len() - CHECKSUM_LENGTH];
+
+    let required = values.len() * 5 / 8;
+
+    if bin.len() < required {
+        return Err(SlugEncodingError::BufferTooSmall { encoding, required })
+    }
+
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let mut len = 0usize;
+
+    for &c in values {
+        acc = ((acc << 5) | value(c).unwrap_or(0) as u32) & 0xfff;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bin[len] = (acc >> bits) as u8;
+            len += 1;
+        }
+    }
+
+    // At most 4 bits of padding, all zero
+    if bits >= 5 || (acc & ((1 << bits) - 1)) != 0 {
+        crate::wipe(&mut bin[..len]);
+        return Err(SlugEncodingError::InvalidPadding { encoding, source: None })
+    }
+    return Ok((hrp, &bin[..len]))
+}
+
+/// # Decode Fives To Slice
+///
+/// Verifies `encoded` and copies its 5-bit values into `fives`, returning the HRP and the values as a sub-slice of `fives`.
+pub fn decode_fives_to_slice<'a, 'b>(encoded: &'a [u8], variant: Bech32Variant, fives: &'b mut [u8]) -> Result<(&'a str, &'b [u8]), SlugEncodingError> {
+    let (hrp, data) = parse(encoded, variant)?;
+    let values = &data[..data.len() - CHECKSUM_LENGTH];
+
+    if fives.len() < values.len() {
+        return Err(SlugEncodingError::BufferTooSmall { encoding: variant.encoding(), required: values.len() })
+    }
+
+    for (v, &c) in fives.iter_mut().zip(values) {
+        *v = value(c).unwrap_or(0);
+    }
+    return Ok((hrp, &fives[..values.len()]))
+}
+
+/// # Encode
+///
+/// Regroups `bin` into 5-bit values and encodes them with `hrp`.
+#[cfg(feature = "alloc")]
+pub fn encode<T: AsRef<[u8]>>(hrp: &str, bin: T, variant: Bech32Variant) -> Result<String, SlugEncodingError> {
+    let bin = bin.as_ref();
+    let mut encoded = vec![0u8; encoded_len(hrp.len(), bin.len())];
+
+    let len = encode_to_slice(hrp, bin, variant, &mut encoded)?.len();
+    return into_string(encoded, len, variant)
+}
+
+/// # Decode
+///
+/// Verifies `encoded` and returns its HRP (lowercase) and data (regrouped into bytes).
+#[cfg(feature = "alloc")]
+pub fn decode<T: AsRef<[u8]>>(encoded: T, variant: Bech32Variant) -> Result<(String, Vec<u8>), SlugEncodingError> {
+    let encoded = encoded.as_ref();
+    let mut bin = vec![0u8; decoded_len(encoded.len())];
+
+    let (hrp, data) = decode_to_slice(encoded, variant, &mut bin)?;
+    let (hrp, len) = (hrp.to_ascii_lowercase(), data.len());
+    bin.truncate(len);
+    return Ok((hrp, bin))
+}
+
+/// # Encode Fives
+///
+/// Encodes 5-bit values (each below 32) with `hrp`.
+#[cfg(feature = "alloc")]
+pub fn encode_fives<T: AsRef<[u8]>>(hrp: &str, fives: T, variant: Bech32Variant) -> Result<String, SlugEncodingError> {
+    let fives = fives.as_ref();
+    let mut encoded = vec![0u8; hrp.len() + 1 + fives.len() + CHECKSUM_LENGTH];
+
+    let len = encode_fives_to_slice(hrp, fives, variant, &mut encoded)?.len();
+    return into_string(encoded, len, variant)
+}
+
+/// # Decode Fives
+///
+/// Verifies `encoded` and returns its HRP (lowercase) and 5-bit values.
+#[cfg(feature = "alloc")]
+pub fn decode_fives<T: AsRef<[u8]>>(encoded: T, variant: Bech32Variant) -> Result<(String, Vec<u8>), SlugEncodingError> {
+    let encoded = encoded.as_ref();
+    let mut fives = vec![0u8; encoded.len()];
+
+    let (hrp, values) = decode_fives_to_slice(encoded, variant, &mut fives)?;
+    let (hrp, len) = (hrp.to_ascii_lowercase(), values.len());
+    fives.truncate(len);
+    return Ok((hrp, fives))
+}
+
+/// # Bytes To Fives
+///
+/// Regroups bytes into 5-bit values (padding the last one with zero bits).
+#[cfg(feature = "alloc")]
+pub fn bytes_to_fives<T: AsRef<[u8]>>(bin: T) -> Vec<u8> {
+    return Fives::new(bin.as_ref()).collect()
+}
+
+/// # Fives To Bytes
+///
+/// Regroups 5-bit values into bytes. Returns `SlugEncodingError::InvalidPadding` if there are more than 4 padding bits or they are not zero, and `DecodingError` for values above 31.
+#[cfg(feature = "alloc")]
+pub fn fives_to_bytes<T: AsRef<[u8]>>(fives: T) -> Result<Vec<u8>, SlugEncodingError> {
+    let fives = fives.as_ref();
+    let encoding = SlugEncodings::Bech32;
+    let mut bin = Vec::with_capacity(fives.len() * 5 / 8);
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+
+    for &v in fives {
+        if v >= 32 {
+            return Err(SlugEncodingError::DecodingError { encoding, source: None })
+        }
+        acc = ((acc << 5) | v as u32) & 0xfff;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            bin.push((acc >> bits) as u8);
+        }
+    }
+
+    if bits >= 5 || (acc & ((1 << bits) - 1)) != 0 {
+        return Err(SlugEncodingError::InvalidPadding { encoding, source: None })
+    }
+    return Ok(bin)
+}
+
+/// # Hrp
+///
+/// A validated human-readable prefix stored inline (so `SlugEncodingUsage` stays `Copy`).
+#[derive(Clone,Copy,PartialEq,Eq,PartialOrd,Hash)]
+pub(crate) struct Hrp {
+    bytes: [u8;MAX_HRP_LENGTH],
+    len: u8,
+}
+
+impl Hrp {
+    pub(crate) const NONE: Hrp = Hrp { bytes: [0u8;MAX_HRP_LENGTH], len: 0 };
+
+    pub(crate) fn new(hrp: &str, encoding: SlugEncodings) -> Result<Self, SlugEncodingError> {
+        check_hrp(hrp.as_bytes(), encoding)?;
+
+        let mut bytes = [0u8;MAX_HRP_LENGTH];
+        bytes[..hrp.len()].copy_from_slice(hrp.as_bytes());
+        return Ok(Self { bytes, len: hrp.len() as u8 })
+    }
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        if self.len == 0 {
+            return None
+        }
+        return core::str::from_utf8(&self.bytes[..self.len as usize]).ok()
+    }
+}
+
+impl fmt::Debug for Hrp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{:?}", self.as_str())
+    }
+}
+
+/// Iterates over the 5-bit values of a byte string (padding the last one with zero bits).
+struct Fives<'a> {
+    bin: &'a [u8],
+    acc: u32,
+    bits: u32,
+}
+
+impl<'a> Fives<'a> {
+    fn new(bin: &'a [u8]) -> Self {
+        return Self { bin, acc: 0, bits: 0 }
+    }
+}
+
+impl Iterator for Fives<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.bits < 5 {
+            match self.bin.split_first() {
+                Some((&b, rest)) => {
+                    self.acc = ((self.acc << 8) | b as u32) & 0xfff;
+                    self.bits += 8;
+                    self.bin = rest;
+                }
+                None if self.bits > 0 => {
+                    let v = (self.acc << (5 - self.bits)) & 31;
+                    self.bits = 0;
+                    return Some(v as u8)
+                }
+                None => return None,
+            }
+        }
+        self.bits -= 5;
+        return Some(((self.acc >> self.bits) & 31) as u8)
+    }
+}
+
+fn polymod_step(chk: u32, value: u8) -> u32 {
+    let top = chk >> 25;
+    let mut chk = ((chk & 0x1ffffff) << 5) ^ value as u32;
+
+    for (i, g) in GENERATOR.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+            chk ^= g;
+        }
+    }
+    return chk
+}
+
+/// The checksum state after the (lowercased) HRP.
+fn hrp_polymod(hrp: &[u8]) -> u32 {
+    let mut chk = 1u32;
+
+    for c in hrp {
+        chk = polymod_step(chk, c.to_ascii_lowercase() >> 5);
+    }
+    chk = polymod_step(chk, 0);
+
+    for c in hrp {
+        chk = polymod_step(chk, c.to_ascii_lowercase() & 31);
+    }
+    return chk
+}
+
+/// Gets the value of a data character (either case).
+fn value(c: u8) -> Option<u8> {
+    let c = c.to_ascii_lowercase();
+    return CHARSET.iter().position(|&symbol| symbol == c).map(|v| v as u8)
+}
+
+fn check_hrp(hrp: &[u8], encoding: SlugEncodings) -> Result<(), SlugEncodingError> {
+    let valid = (1..=MAX_HRP_LENGTH).contains(&hrp.len())
+        && hrp.iter().all(|c| (33..=126).contains(c))
+        && !(hrp.iter().any(u8::is_ascii_lowercase) && hrp.iter().any(u8::is_ascii_uppercase));
+
+    match valid {
+        true => Ok(()),
+        false => Err(SlugEncodingError::InvalidHrp { encoding }),
+    }
+}
+
+fn encode_values<'a, I: Iterator<Item = u8>>(hrp: &str, values: I, values_len: usize, variant: Bech32Variant, encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+    let encoding = variant.encoding();
+    let hrp = hrp.as_bytes();
+    check_hrp(hrp, encoding)?;
+
+    let length = hrp.len() + 1 + values_len + CHECKSUM_LENGTH;
+
+    if length > MAX_LENGTH {
+        return Err(SlugEncodingError::InvalidLength { encoding, length, source: None })
+    }
+    if encoded.len() < length {
+        return Err(SlugEncodingError::BufferTooSmall { encoding, required: length })
+    }
+
+    encoded[..hrp.len()].copy_from_slice(hrp);
+    encoded[hrp.len()] = b'1';
+
+    let mut chk = hrp_polymod(hrp);
+    let mut len = hrp.len() + 1;
+
+    for v in values {
+        chk = polymod_step(chk, v);
+        encoded[len] = CHARSET[v as usize];
+        len += 1;
+    }
+    for _ in 0..CHECKSUM_LENGTH {
+        chk = polymod_step(chk, 0);
+    }
+    chk ^= variant.constant();
+
+    for i in 0..CHECKSUM_LENGTH {
+        encoded[len] = CHARSET[((chk >> (5 * (5 - i))) & 31) as usize];
+        len += 1;
+    }
+
+    // Uppercase HRPs give uppercase strings (e.g. `AGE-SECRET-KEY-1...`)
+    if hrp.iter().any(u8::is_ascii_uppercase) {
+        encoded[hrp.len()..len].make_ascii_uppercase();
+    }
+    return core::str::from_utf8(&encoded[..len]).map_err(|_| SlugEncodingError::EncodingError { encoding, source: None })
+}
+
+/// Validates `encoded` and its checksum, returning the HRP and the data characters (including the checksum).
+fn parse(encoded: &[u8], variant: Bech32Variant) -> Result<(&str, &[u8]), SlugEncodingError> {
+    let encoding = variant.encoding();
+    let length = encoded.len();
+
+    if length > MAX_LENGTH {
+        return Err(SlugEncodingError::InvalidLength { encoding, length, source: None })
+    }
+    if let Some(index) = encoded.iter().position(|c| !(33..=126).contains(c)) {
+        return Err(invalid_character(encoding, encoded, index, None))
+    }
+
+    // All lowercase or all uppercase
+    let upper = encoded.iter().find(|c| c.is_ascii_alphabetic()).is_some_and(u8::is_ascii_uppercase);
+
+    if let Some(index) = encoded.iter().position(|c| c.is_ascii_alphabetic() && c.is_ascii_uppercase() != upper) {
+        return Err(invalid_character(encoding, encoded, index, None))
+    }
+
+    let separator = match encoded.iter().rposition(|&c| c == b'1') {
+        Some(separator) if separator > 0 && separator <= MAX_HRP_LENGTH => separator,
+        _ => return Err(SlugEncodingError::InvalidHrp { encoding }),
+    };
+    let (hrp, data) = (&encoded[..separator], &encoded[separator + 1..]);
+
+    if data.len() < CHECKSUM_LENGTH {
+        return Err(SlugEncodingError::InvalidLength { encoding, length, source: None })
+    }
+    if let Some(index) = data.iter().position(|&c| value(c).is_none()) {
+        return Err(invalid_character(encoding, encoded, separator + 1 + index, None))
+    }
+
+    let chk = data.iter().fold(hrp_polymod(hrp), |chk, &c| polymod_step(chk, value(c).unwrap_or(0)));
+
+    if chk != variant.constant() {
+        let index = locate_error(chk ^ variant.constant(), data.len()).map(|index| separator + 1 + index);
+        return Err(SlugEncodingError::InvalidChecksum { encoding, index })
+    }
+
+    let hrp = core::str::from_utf8(hrp).map_err(|_| SlugEncodingError::InvalidHrp { encoding })?;
+    return Ok((hrp, data))
+}
+
+/// # Locate Error
+///
+/// The checksum is linear, so a wrong value `d` at `k` positions from the end changes the residue by the checksum of `d` followed by `k` zeros. Returns the index (in the data part) of the only single substitution that explains `residue`, if there is one.
+fn locate_error(residue: u32, len: usize) -> Option<usize> {
+    let mut syndromes: [u32;32] = core::array::from_fn(|d| d as u32);
+    let mut found = None;
+
+    for k in 0..len {
+        if syndromes[1..].contains(&residue) {
+            if found.is_some() {
+                return None
+            }
+            found = Some(len - 1 - k);
+        }
+        for syndrome in syndromes[1..].iter_mut() {
+            *syndrome = polymod_step(*syndrome, 0);
+        }
+    }
+    return found
+}
+
+#[cfg(feature = "alloc")]
+fn into_string(mut encoded: Vec<u8>, len: usize, variant: Bech32Variant) -> Result<String, SlugEncodingError> {
+    encoded.truncate(len);
+    return String::from_utf8(encoded).map_err(|_| SlugEncodingError::EncodingError { encoding: variant.encoding(), source: None })
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bech32_vectors() {
+    let mut buf = [0u8;MAX_LENGTH];
+
+    // BIP-173 and BIP-350 valid strings
+    let valid = [
+        (Bech32Variant::Bech32, "A12UEL5L"),
+        (Bech32Variant::Bech32, "a12uel5l"),
+        (Bech32Variant::Bech32, "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs"),
+        (Bech32Variant::Bech32, "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"),
+        (Bech32Variant::Bech32, "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j"),
+        (Bech32Variant::Bech32, "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w"),
+        (Bech32Variant::Bech32m, "A1LQFN3A"),
+        (Bech32Variant::Bech32m, "a1lqfn3a"),
+        (Bech32Variant::Bech32m, "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx"),
+        (Bech32Variant::Bech32m, "split1checkupstagehandshakeupstreamerranterredcaperredlc445v"),
+        (Bech32Variant::Bech32m, "?1v759aa"),
+    ];
+
+    for (variant, encoded) in valid {
+        let (hrp, fives) = decode_fives_to_slice(encoded.as_bytes(), variant, &mut buf).unwrap();
+        let (hrp, fives) = (hrp.to_ascii_lowercase(), fives.to_vec());
+        let mut out = [0u8;MAX_LENGTH];
+        assert_eq!(encode_fives_to_slice(&hrp, &fives, variant, &mut out).unwrap(), encoded.to_ascii_lowercase());
+
+        // The other variant rejects it
+        let other = if variant == Bech32Variant::Bech32 { Bech32Variant::Bech32m } else { Bech32Variant::Bech32 };
+        assert!(matches!(decode_fives_to_slice(encoded.as_bytes(), other, &mut buf), Err(SlugEncodingError::InvalidChecksum { .. })));
+    }
+
+    // Segwit v0 (BIP-173): the witness version, then the regrouped program
+    let (hrp, fives) = decode_fives_to_slice(b"BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", Bech32Variant::Bech32, &mut buf).unwrap();
+    assert_eq!((hrp, fives[0]), ("BC", 0));
+    assert_eq!(fives_to_bytes(&fives[1..]).unwrap(), [0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c, 0x45, 0xd1, 0xb3, 0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6]);
+}
+
+#[test]
+fn bech32_errors() {
+    let mut buf = [0u8;MAX_LENGTH];
+    let decode = |s: &str| decode_to_slice(s.as_bytes(), Bech32Variant::Bech32, &mut [0u8;MAX_LENGTH]).map(|(hrp, data)| (hrp.len(), data.len()));
+
+    // A single substitution is located
+    assert_eq!(decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxx"), Err(SlugEncodingError::InvalidChecksum { encoding: SlugEncodings::Bech32, index: Some(44) }));
+    assert_eq!(decode("abcdef1qpzrz9x8gf2tvdw0s3jn54khce6mua7lmqqqxw"), Err(SlugEncodingError::InvalidChecksum { encoding: SlugEncodings::Bech32, index: Some(11) }));
+
+    assert!(matches!(decode("A1G7SGD8"), Err(SlugEncodingError::InvalidChecksum { .. })));
+    assert!(matches!(decode("pzry9x0s0muk"), Err(SlugEncodingError::InvalidHrp { .. })));
+    assert!(matches!(decode("1pzry9x0s0muk"), Err(SlugEncodingError::InvalidHrp { .. })));
+    assert!(matches!(decode("li1dgmt3"), Err(SlugEncodingError::InvalidLength { .. })));
+    assert!(matches!(decode("x1b4n0q5v"), Err(SlugEncodingError::InvalidCharacter { index: 2, character: 'b', .. })));
+    assert!(matches!(decode("A1g7sgd8"), Err(SlugEncodingError::InvalidCharacter { index: 2, .. })));
+    assert!(matches!(decode("\u{7f}1axkwrx"), Err(SlugEncodingError::InvalidCharacter { index: 0, .. })));
+
+    assert!(matches!(encode_to_slice("", b"", Bech32Variant::Bech32, &mut buf), Err(SlugEncodingError::InvalidHrp { .. })));
+    assert!(matches!(encode_to_slice("Ab", b"", Bech32Variant::Bech32, &mut buf), Err(SlugEncodingError::InvalidHrp { .. })));
+    assert!(matches!(encode_to_slice("a", &[0u8;60], Bech32Variant::Bech32, &mut [0u8;128]), Err(SlugEncodingError::InvalidLength { .. })));
+    assert!(matches!(encode_to_slice("a", &[0u8;4], Bech32Variant::Bech32, &mut [0u8;8]), Err(SlugEncodingError::BufferTooSmall { required: 15, .. })));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn bech32_roundtrip() {
+    for len in 0..=40 {
+        let data: Vec<u8> = (0..len as u8).map(|i| i.wrapping_mul(37)).collect();
+
+        for variant in [Bech32Variant::Bech32, Bech32Variant::Bech32m] {
+            let encoded = encode("npub", &data, variant).unwrap();
+            assert_eq!(encoded.len(), encoded_len(4, len));
+            assert_eq!(decode(&encoded, variant).unwrap(), ("npub".to_string(), data.clone()));
+            assert_eq!(decode(encoded.to_uppercase(), variant).unwrap().1, data);
+        }
+    }
+
+    assert_eq!(fives_to_bytes(bytes_to_fives([0xff, 0x01])).unwrap(), [0xff, 0x01]);
+    assert!(matches!(fives_to_bytes([31, 31, 31, 31]), Err(SlugEncodingError::InvalidPadding { .. })));
+
+    // Uppercase HRPs produce uppercase strings
+    let key = encode("AGE-SECRET-KEY-", [7u8;32], Bech32Variant::Bech32).unwrap();
+    assert!(key.starts_with("AGE-SECRET-KEY-1") && !key.chars().any(|c| c.is_ascii_lowercase()));
+    assert_eq!(decode(&key, Bech32Variant::Bech32).unwrap(), ("age-secret-key-".to_string(), vec![7u8;32]));
+}
@@ -0,0 +1,392 @@
+//! # slugencode
+//!
+//! Encodes, decodes and transcodes stdin (or a file) with any `SlugEncodings` variant.
+//!
+//! ```text
+//! slugencode --to base64 < file.bin
+//! slugencode --from hex secret.txt > secret.bin
+//! slugencode --from base58 --to hex <<< 2NEpo7TZRRrLZSi2U
+//! slugencode --from auto --to base64url --constant-time
+//! slugencode --from hex --to bech32 --hrp npub <<< 7e7e9c42a91bfef19fa929e5fda1b72e0ebc1a4c1141673e2794234d86addf4e
+//! slugencode --from base58 --alphabet ripple --to hex <<< rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh
+//! ```
+//!
+//! Exit codes follow `sysexits.h`: 64 for usage errors (including encodings rejected by `--constant-time`), 65 for invalid input, 66 for a missing input file, 74 for I/O errors and 70 for anything else.
+
+#![allow(clippy::needless_return)]
+
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::process::ExitCode;
+
+use slugencode::prelude::*;
+
+const USAGE: &str = "Usage: slugencode [OPTIONS] [FILE]
+
+Encodes, decodes or transcodes FILE (or stdin when FILE is missing or `-`).
+
+Options:
+  -e, --encode, --to ENCODING     Encode the input (or the decoded input) with ENCODING
+  -d, --decode, --from ENCODING   Decode the input with ENCODING (`auto` detects it)
+  -w, --wrap COLUMNS              Wrap encoded output after COLUMNS characters (0 disables)
+  -p, --hrp HRP                   Human-readable prefix for Bech32 (required to encode, checked when decoding)
+  -a, --alphabet ALPHABET         Base58 alphabet: bitcoin (default), ripple, flickr or the 58 symbols
+  -c, --constant-time             Only allow constant-time encodings (and no `auto`)
+  -l, --list                      List the encodings
+  -h, --help                      Show this message
+
+Input is decoded after removing whitespace. Decoded output is written as raw bytes.";
+
+/// The encoding given to `--from`.
+#[derive(Clone,Copy,Debug,PartialEq)]
+enum Source {
+    Auto,
+    Encoding(SlugEncodings),
+}
+
+/// What to do with the input.
+#[derive(Clone,Debug,Default,PartialEq)]
+struct Options {
+    from: Option<Source>,
+    to: Option<SlugEncodings>,
+    wrap: usize,
+    hrp: Option<String>,
+    alphabet: Base58Alphabet,
+    constant_time: bool,
+    list: bool,
+    help: bool,
+    file: Option<String>,
+}
+
+/// Errors and their exit codes.
+#[derive(Debug)]
+enum CliError {
+    Usage(String),
+    NoInput(String, io::Error),
+    Io(io::Error),
+    Encoding(SlugEncodingError),
+}
+
+impl CliError {
+    /// Gets the exit code (from `sysexits.h`)
+    fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) => 64,
+            CliError::NoInput(..) => 66,
+            CliError::Io(_) => 74,
+            CliError::Encoding(e) => match e {
+                SlugEncodingError::DecodingError { .. } |
+                SlugEncodingError::InvalidCharacter { .. } |
+                SlugEncodingError::InvalidPadding { .. } |
+                SlugEncodingError::InvalidLength { .. } |
+                SlugEncodingError::LengthMismatch { .. } |
+                SlugEncodingError::InputTooLong { .. } |
+                SlugEncodingError::Overflow { .. } |
+                SlugEncodingError::ChecksumMismatch |
+                SlugEncodingError::InvalidHrp { .. } |
+                SlugEncodingError::InvalidChecksum { .. } |
+                SlugEncodingError::InvalidVersion { .. } |
+                SlugEncodingError::UnknownEncoding |
+                SlugEncodingError::AmbiguousEncoding => 65,
+                _ => 70,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::Usage(message) => write!(f, "{}", message),
+            CliError::NoInput(file, e) => write!(f, "{}: {}", file, e),
+            CliError::Io(e) => write!(f, "{}", e),
+            CliError::Encoding(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        // Errors from `SlugEncoderWriter` wrap a `SlugEncodingError`
+        match e.get_ref().and_then(|inner| inner.downcast_ref::<SlugEncodingError>()) {
+            Some(inner) => CliError::Encoding(inner.clone()),
+            None => CliError::Io(e),
+        }
+    }
+}
+
+impl From<SlugEncodingError> for CliError {
+    fn from(e: SlugEncodingError) -> Self {
+        CliError::Encoding(e)
+    }
+}
+
+fn main() -> ExitCode {
+    let result = parse_args(std::env::args().skip(1)).and_then(|options| run(&options));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        // The reader of our output went away (e.g. `| head`)
+        Err(CliError::Io(e)) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("slugencode: {}", e);
+
+            if matches!(e, CliError::Usage(_)) {
+                eprintln!("Try `slugencode --help` for more information.");
+            }
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+/// Parses the command-line arguments (without the program name).
+fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, CliError> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    let mut only_files = false;
+
+    while let Some(arg) = args.next() {
+        // Accepts `--to=hex` as well as `--to hex`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") && !only_files => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next()).ok_or_else(|| CliError::Usage(format!("{} requires a value", flag)));
+
+        match flag.as_str() {
+            _ if only_files || flag == "-" || !flag.starts_with('-') => {
+                if options.file.replace(arg).is_some() {
+                    return Err(CliError::Usage("more than one input file".to_string()))
+                }
+            }
+            "--" => only_files = true,
+            "-e" | "--encode" | "--to" => options.to = Some(parse_encoding(&value()?)?),
+            "-d" | "--decode" | "--from" => {
+                let name = value()?;
+                options.from = Some(if name.eq_ignore_ascii_case("auto") { Source::Auto } else { Source::Encoding(parse_encoding(&name)?) });
+            }
+            "-w" | "--wrap" => {
+                let columns = value()?;
+                options.wrap = columns.parse().map_err(|_| CliError::Usage(format!("invalid number of columns `{}`", columns)))?;
+            }
+            "-p" | "--hrp" => {
+                let hrp = value()?;
+                SlugEncodingUsage::new(SlugEncodings::Bech32).with_hrp(&hrp).map_err(|_| CliError::Usage(format!("invalid human-readable prefix `{}`", hrp)))?;
+                options.hrp = Some(hrp);
+            }
+            "-a" | "--alphabet" => {
+                let alphabet = value()?;
+                options.alphabet = alphabet.parse().map_err(|e| CliError::Usage(format!("invalid Base58 alphabet `{}`: {}", alphabet, e)))?;
+            }
+            "-c" | "--constant-time" => options.constant_time = true,
+            "-l" | "--list" => options.list = true,
+            "-h" | "--help" => options.help = true,
+            _ => return Err(CliError::Usage(format!("unknown option `{}`", arg))),
+        }
+    }
+
+    if !(options.help || options.list) && options.from.is_none() && options.to.is_none() {
+        return Err(CliError::Usage("one of --encode/--to or --decode/--from is required".to_string()))
+    }
+    if let Some(to) = options.to.filter(|to| matches!(to, SlugEncodings::Bech32 | SlugEncodings::Bech32m) && options.hrp.is_none()) {
+        return Err(CliError::Usage(format!("{} requires --hrp", to)))
+    }
+    if options.constant_time {
+        if options.from == Some(Source::Auto) {
+            return Err(CliError::Usage("`auto` is not allowed with --constant-time".to_string()))
+        }
+        let encodings = [options.from, options.to.map(Source::Encoding)];
+
+        if let Some(encoding) = encodings.into_iter().flatten().find_map(|source| match source {
+            Source::Encoding(encoding) if !encoding.is_constant_time() => Some(encoding),
+            _ => None,
+        }) {
+            return Err(CliError::Usage(format!("{} is not constant-time", encoding)))
+        }
+    }
+    return Ok(options)
+}
+
+fn parse_encoding(name: &str) -> Result<SlugEncodings, CliError> {
+    return name.parse().map_err(|_| CliError::Usage(format!("unknown encoding `{}` (see --list)", name)))
+}
+
+impl Options {
+    /// Gets the usage for `encoding` (with `--hrp` and `--alphabet` if they were given)
+    fn usage(&self, encoding: SlugEncodings) -> Result<SlugEncodingUsage, CliError> {
+        let usage = SlugEncodingUsage::new(encoding).with_alphabet(self.alphabet);
+
+        match &self.hrp {
+            Some(hrp) => return Ok(usage.with_hrp(hrp)?),
+            None => return Ok(usage),
+        }
+    }
+}
+
+fn run(options: &Options) -> Result<(), CliError> {
+    let stdout = io::stdout();
+    let mut output = BufWriter::new(stdout.lock());
+
+    if options.help {
+        writeln!(output, "{}", USAGE)?;
+        return Ok(output.flush()?)
+    }
+    if options.list {
+        for encoding in SlugEncodings::all().iter().filter(|encoding| !options.constant_time || encoding.is_constant_time()) {
+            let constant_time = if encoding.is_constant_time() { "constant-time" } else { "not constant-time" };
+            let line = format!("{:<16} {:<18} {}", encoding.name(), constant_time, encoding.aliases().join(", "));
+            writeln!(output, "{}", line.trim_end())?;
+        }
+        return Ok(output.flush()?)
+    }
+
+    let mut input: Box<dyn Read> = match options.file.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(file) => Box::new(File::open(file).map_err(|e| CliError::NoInput(file.to_string(), e))?),
+    };
+
+    match (options.from, options.to) {
+        // Encode only (streamed when the encoding allows it)
+        (None, Some(to)) => {
+            let mut wrapped = LineWrap::new(&mut output, options.wrap);
+
+            let streamed = match options.usage(to)?.encoder_writer(&mut wrapped) {
+                Ok(mut writer) => {
+                    io::copy(&mut input, &mut writer)?;
+                    writer.finish()?;
+                    true
+                }
+                Err(SlugEncodingError::StreamingUnsupported { .. }) => false,
+                Err(e) => return Err(e.into()),
+            };
+
+            if !streamed {
+                let mut bytes = Vec::new();
+                input.read_to_end(&mut bytes)?;
+                wrapped.write_all(options.usage(to)?.encode(bytes)?.as_bytes())?;
+            }
+            wrapped.finish()?;
+        }
+        // Transcode (the decoded bytes are never handed to us)
+        (Some(Source::Encoding(from)), Some(to)) => {
+            let mut wrapped = LineWrap::new(&mut output, options.wrap);
+            wrapped.write_all(SlugEncodingUsage::transcode(read_encoded(&mut input)?, options.usage(from)?, options.usage(to)?)?.as_bytes())?;
+            wrapped.finish()?;
+        }
+        // Decode, then either write the bytes or encode them again
+        (Some(from), to) => {
+            let decoded = decode(options, from, &read_encoded(&mut input)?)?;
+
+            match to {
+                Some(to) => {
+                    let mut wrapped = LineWrap::new(&mut output, options.wrap);
+                    wrapped.write_all(options.usage(to)?.encode(decoded)?.as_bytes())?;
+                    wrapped.finish()?;
+                }
+                None => output.write_all(&decoded)?,
+            }
+        }
+        (None, None) => unreachable!("checked by parse_args"),
+    }
+    return Ok(output.flush()?)
+}
+
+/// Reads the whole input, removing whitespace (line breaks from wrapping, a trailing newline).
+fn read_encoded<R: Read>(input: &mut R) -> Result<Vec<u8>, CliError> {
+    let mut encoded = Vec::new();
+    input.read_to_end(&mut encoded)?;
+    encoded.retain(|c| !c.is_ascii_whitespace());
+    return Ok(encoded)
+}
+
+fn decode(options: &Options, from: Source, encoded: &[u8]) -> Result<Vec<u8>, CliError> {
+    match from {
+        Source::Encoding(encoding) => return Ok(options.usage(encoding)?.decode(encoded)?),
+        Source::Auto => {
+            let encoded = std::str::from_utf8(encoded).map_err(|_| SlugEncodingError::UnknownEncoding)?;
+            let candidate = SlugEncodingUsage::decode_any(encoded)?;
+            eprintln!("slugencode: detected {} ({}% confidence)", candidate.encoding, candidate.confidence);
+            return Ok(candidate.decoded)
+        }
+    }
+}
+
+/// # LineWrap
+///
+/// Inserts a newline every `columns` bytes (never if `columns` is 0). `finish()` ends the output with a newline.
+struct LineWrap<W: Write> {
+    inner: W,
+    columns: usize,
+    column: usize,
+}
+
+impl<W: Write> LineWrap<W> {
+    fn new(inner: W, columns: usize) -> Self {
+        return Self { inner, columns, column: 0 }
+    }
+    /// Writes the final newline and flushes
+    fn finish(mut self) -> io::Result<W> {
+        if self.column > 0 || self.columns == 0 {
+            self.inner.write_all(b"\n")?;
+        }
+        self.inner.flush()?;
+        return Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for LineWrap<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.columns == 0 {
+            return self.inner.write(buf)
+        }
+        let mut rest = buf;
+
+        while !rest.is_empty() {
+            if self.column == self.columns {
+                self.inner.write_all(b"\n")?;
+                self.column = 0;
+            }
+            let len = rest.len().min(self.columns - self.column);
+            self.inner.write_all(&rest[..len])?;
+            self.column += len;
+            rest = &rest[len..];
+        }
+        return Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        return self.inner.flush()
+    }
+}
+
+#[test]
+fn cli_arguments() {
+    let parse = |args: &[&str]| parse_args(args.iter().map(|arg| arg.to_string()));
+
+    let options = parse(&["--from", "b58", "--to=hex", "-w", "76", "input.txt"]).unwrap();
+    assert_eq!(options.from, Some(Source::Encoding(SlugEncodings::Base58)));
+    assert_eq!(options.to, Some(SlugEncodings::Hex));
+    assert_eq!((options.wrap, options.file.as_deref()), (76, Some("input.txt")));
+
+    assert_eq!(parse(&["-d", "auto", "--", "-file"]).unwrap().file.as_deref(), Some("-file"));
+    assert_eq!(parse(&["-c", "-e", "base64"]).unwrap().to, Some(SlugEncodings::Base64));
+    assert_eq!(parse(&["-e", "bech32m", "--hrp=age"]).unwrap().hrp.as_deref(), Some("age"));
+    assert_eq!(parse(&["-d", "base58", "-a", "Flickr"]).unwrap().alphabet, Base58Alphabet::FLICKR);
+
+    for args in [&[][..], &["-e", "base65"], &["-e"], &["-c", "-e", "z85"], &["-c", "-d", "auto"], &["-e", "hex", "a", "b"], &["--wrap", "x", "-e", "hex"], &["-e", "bech32"], &["-e", "bech32", "-p", "Mixed"], &["-e", "base58", "-a", "abc"]] {
+        assert_eq!(parse(args).unwrap_err().exit_code(), 64, "{:?}", args);
+    }
+}
+
+#[test]
+fn cli_line_wrap() {
+    let mut wrapped = LineWrap::new(Vec::new(), 4);
+    wrapped.write_all(b"abcdef").unwrap();
+    wrapped.write_all(b"gh").unwrap();
+    assert_eq!(wrapped.finish().unwrap(), b"abcd\nefgh\n");
+
+    let mut wrapped = LineWrap::new(Vec::new(), 0);
+    wrapped.write_all(b"abcdef").unwrap();
+    assert_eq!(wrapped.finish().unwrap(), b"abcdef\n");
+
+    assert_eq!(CliError::from(io::Error::new(io::ErrorKind::InvalidData, SlugEncodingError::UnknownEncoding)).exit_code(), 65);
+}
@@ -0,0 +1,129 @@
+//! # Try Get Encoding
+//!
+//! Guesses the encoding of an opaque string by trying every detectable `SlugEncodings` variant and scoring the ones that decode successfully.
+//!
+//! The confidence is a score from 0 to 100. Bech32 and Bech32m score highest since their checksum rules out a coincidental match. Smaller alphabets and distinguishing characters (like `=` padding, `+`/`/` for Base64 or `-`/`_` for Base64 URL Safe) score higher, since they are less likely to match by coincidence.
+//!
+//! Z85, Base85 (RFC1924), Base58 (Fast) and Base58 (Monero) are not detected as they overlap almost entirely with other alphabets.
+
+use crate::SlugEncodings;
+use crate::SlugDecoder;
+
+use alloc::vec::Vec;
+
+/// Candidates closer than this to the best confidence (that decode to different bytes) make a match ambiguous.
+pub const AMBIGUITY_MARGIN: u8 = 10;
+
+/// # SlugEncodingCandidate
+///
+/// A possible encoding of the input along with its confidence (0-100) and the decoded bytes.
+#[derive(Clone,Debug,PartialEq,Hash)]
+pub struct SlugEncodingCandidate {
+    pub encoding: SlugEncodings,
+    pub confidence: u8,
+    pub decoded: Vec<u8>,
+}
+
+/// # Try Get Encoding
+///
+/// Returns the candidate encodings of `input` ranked from the highest confidence to the lowest. The list is empty if nothing decodes.
+///
+/// ```rust
+/// use slugencode::detect::try_get_encoding;
+/// use slugencode::SlugEncodings;
+///
+/// let candidates = try_get_encoding("deadbeef");
+/// assert_eq!(candidates[0].encoding, SlugEncodings::Hex);
+/// assert_eq!(candidates[0].decoded, vec![0xde, 0xad, 0xbe, 0xef]);
+/// ```
+pub fn try_get_encoding<T: AsRef<str>>(input: T) -> Vec<SlugEncodingCandidate> {
+    let input = input.as_ref();
+    let mut candidates: Vec<SlugEncodingCandidate> = Vec::new();
+
+    if input.is_empty() {
+        return candidates
+    }
+
+    let has = |chars: &[char]| input.contains(chars);
+    let has_lower = input.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = input.chars().any(|c| c.is_ascii_uppercase());
+
+    let attempts: [(SlugEncodings, Option<Vec<u8>>, u8); 9] = [
+        (SlugEncodings::Bech32, input.from_bech32().ok().map(|(_, data)| data), 98),
+        (SlugEncodings::Bech32m, input.from_bech32m().ok().map(|(_, data)| data), 98),
+        (SlugEncodings::Hex, input.from_hex().ok(), if has_lower && has_upper { 60 } else { 90 }),
+        (SlugEncodings::Ascii85, decode_delimited_ascii85(input), 95),
+        (SlugEncodings::Base32, input.from_bs32().ok(), if has(&['=']) { 85 } else { 75 }),
+        (SlugEncodings::Base32unpadded, input.from_bs32_unpadded().ok(), 70),
+        (SlugEncodings::Base64, input.from_bs64().ok(), if has(&['+', '/']) { 85 } else if has(&['=']) { 70 } else { 55 }),
+        (SlugEncodings::Base64urlsafe, input.from_bs64_url().ok(), if has(&['-', '_']) { 85 } else if has(&['=']) { 70 } else { 55 }),
+        (SlugEncodings::Base58, input.from_base58().ok(), if has_lower && has_upper { 65 } else { 60 }),
+    ];
+
+    for (encoding, decoded, confidence) in attempts {
+        if let Some(decoded) = decoded {
+            candidates.push(SlugEncodingCandidate { encoding, confidence, decoded });
+        }
+    }
+
+    // Stable sort keeps the order above for equal confidence
+    candidates.sort_by_key(|candidate| core::cmp::Reverse(candidate.confidence));
+    candidates
+}
+
+/// Ascii85 is only detected with its `<~` and `~>` delimiters.
+fn decode_delimited_ascii85(input: &str) -> Option<Vec<u8>> {
+    if input.starts_with(crate::base85::ASCII85_PREFIX) && input.ends_with(crate::base85::ASCII85_SUFFIX) {
+        return input.from_ascii85().ok()
+    }
+    None
+}
+
+#[test]
+fn detect_distinguishing_characters() {
+    let bytes = [0xfb, 0xff, 0xbf, 0x01];
+
+    let base64 = try_get_encoding("+/+/AQ==");
+    assert_eq!(base64[0].encoding, SlugEncodings::Base64);
+    assert_eq!(base64[0].decoded, bytes);
+
+    let base64_url = try_get_encoding("-_-_AQ==");
+    assert_eq!(base64_url[0].encoding, SlugEncodings::Base64urlsafe);
+    assert_eq!(base64_url[0].decoded, bytes);
+
+    let ascii85 = try_get_encoding("<~9jqo^Bla~>");
+    assert_eq!(ascii85[0].encoding, SlugEncodings::Ascii85);
+    assert_eq!(ascii85[0].decoded, b"Man is");
+
+    let base58 = try_get_encoding("1FfmbHfnpaZjKFvyi1okTjJJusN455paPH");
+    assert_eq!(base58[0].encoding, SlugEncodings::Base58);
+
+    let bech32 = try_get_encoding("age1qqqsyqcyq5rqwzqfpg9scrgwpugpzysnzs23v9ccrydpk8qarc0savhh7m");
+    assert_eq!(bech32[0].encoding, SlugEncodings::Bech32);
+}
+
+#[test]
+fn detect_invalid_base32_lengths() {
+    assert!(try_get_encoding("aaaaaa==").iter().all(|c| c.encoding != SlugEncodings::Base32));
+    assert!(try_get_encoding("a").iter().all(|c| c.encoding != SlugEncodings::Base32unpadded));
+}
+
+#[test]
+fn detect_nothing() {
+    assert!(try_get_encoding("").is_empty());
+    assert!(try_get_encoding("not valid!").is_empty());
+}
+
+#[test]
+fn detect_decode_any() {
+    use crate::errors::SlugEncodingError;
+    use crate::SlugEncodingUsage;
+
+    let decoded = SlugEncodingUsage::decode_any("4144675a6e958d60").unwrap();
+    assert_eq!(decoded.encoding, SlugEncodings::Hex);
+    assert_eq!(decoded.decoded, "4144675a6e958d60".from_hex().unwrap());
+
+    // Mixed-case hex is as likely to be Base58
+    assert_eq!(SlugEncodingUsage::decode_any("DeadBeef"), Err(SlugEncodingError::AmbiguousEncoding));
+    assert_eq!(SlugEncodingUsage::decode_any("not valid!"), Err(SlugEncodingError::UnknownEncoding));
+}
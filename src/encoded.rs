@@ -0,0 +1,276 @@
+//! # Encoded Strings
+//!
+//! Requires the `alloc` feature.
+//!
+//! `Encoded<E>` is a string that is known to be valid in the encoding `E`. It can only be built from valid input (`new`, `FromStr`, `TryFrom`, serde) or from bytes (`from_bytes`), so it is validated once instead of wherever it is passed, and `as_bytes()` never fails.
+//!
+//! - `HexString`, `Base32String`, `Base58String`, `Base64String` and `Base64UrlString` are aliases for the common encodings (every `SlugEncodings` variant has a marker type, e.g. `Encoded<Z85>`).
+//! - Equality compares the decoded bytes in constant-time (`subtle::ConstantTimeEq`), so comparing a received token against an expected one does not leak where they differ. Only the lengths are compared in variable time.
+//! - The string is kept as given (e.g. uppercase hex), while equality and hashing use the decoded bytes.
+//! - The decoded bytes are wiped on drop.
+//! - With the `serde` feature it (de)serializes as the encoded string, validating it when deserializing.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::str::FromStr;
+
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::errors::SlugEncodingError;
+use crate::{wipe, SlugEncodingUsage, SlugEncodings};
+
+use alloc::{string::String, vec::Vec};
+
+/// # SlugEncodingKind
+///
+/// Ties a marker type (e.g. `Hex`) to a `SlugEncodings` variant.
+pub trait SlugEncodingKind {
+    /// The encoding
+    const ENCODING: SlugEncodings;
+}
+
+macro_rules! encoding_kind {
+    ($name:ident, $doc:literal) => {
+        #[doc = $doc]
+        #[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+        pub enum $name {}
+
+        impl SlugEncodingKind for $name {
+            const ENCODING: SlugEncodings = SlugEncodings::$name;
+        }
+    };
+}
+
+encoding_kind!(Hex, "Hexadecimal (Constant-Time)");
+encoding_kind!(Base32, "Base32 (Constant-Time)");
+encoding_kind!(Base32unpadded, "Base32 Unpadded (Constant-Time)");
+encoding_kind!(Base58, "Base58 (Constant-Time)");
+encoding_kind!(Base58fast, "Base58 Fast (Not Constant-Time)");
+encoding_kind!(Base58monero, "Base58 Monero (Constant-Time)");
+encoding_kind!(Base64, "Base64 (Constant-Time)");
+encoding_kind!(Base64urlsafe, "Base64 URL Safe (Constant-Time)");
+encoding_kind!(Ascii85, "Ascii85 (Not Constant-Time)");
+encoding_kind!(Z85, "Z85 (Not Constant-Time)");
+encoding_kind!(Base85rfc1924, "Base85 RFC1924 (Not Constant-Time)");
+encoding_kind!(Bech32, "Bech32 (Not Constant-Time) (any human-readable prefix)");
+encoding_kind!(Bech32m, "Bech32m (Not Constant-Time) (any human-readable prefix)");
+
+/// A valid hexadecimal string
+pub type HexString = Encoded<Hex>;
+/// A valid Base32 string
+pub type Base32String = Encoded<Base32>;
+/// A valid Base58 string
+pub type Base58String = Encoded<Base58>;
+/// A valid Base64 string
+pub type Base64String = Encoded<Base64>;
+/// A valid Base64 URL Safe string
+pub type Base64UrlString = Encoded<Base64urlsafe>;
+
+/// # Encoded
+///
+/// A string that is valid in the encoding `E` (along with its decoded bytes).
+///
+/// ## Example Code
+///
+/// ```rust
+/// use slugencode::prelude::*;
+///
+/// fn main() {
+///     let token: HexString = "DEADBEEF".parse().unwrap();
+///     assert_eq!(token.as_bytes(), [0xde, 0xad, 0xbe, 0xef]);
+///
+///     // Compares the decoded bytes in constant-time
+///     assert_eq!(token, HexString::from_bytes([0xde, 0xad, 0xbe, 0xef]).unwrap());
+///
+///     assert!("deadbeeg".parse::<HexString>().is_err());
+/// }
+/// ```
+pub struct Encoded<E: SlugEncodingKind> {
+    encoded: String,
+    decoded: Vec<u8>,
+    kind: PhantomData<fn() -> E>,
+}
+
+impl<E: SlugEncodingKind> Encoded<E> {
+    /// Validates `encoded_str` (returning the decoding error if it is not valid)
+    pub fn new<T: Into<String>>(encoded_str: T) -> Result<Self, SlugEncodingError> {
+        let encoded = encoded_str.into();
+        let decoded = SlugEncodingUsage::new(E::ENCODING).decode(&encoded)?;
+
+        return Ok(Self { encoded, decoded, kind: PhantomData })
+    }
+    /// Encodes `bytes` (fails for Bech32, which needs a human-readable prefix)
+    pub fn from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, SlugEncodingError> {
+        let decoded = bytes.as_ref().to_vec();
+        let encoded = SlugEncodingUsage::new(E::ENCODING).encode(&decoded)?;
+
+        return Ok(Self { encoded, decoded, kind: PhantomData })
+    }
+    /// Gets encoding
+    pub fn get_encoding(&self) -> SlugEncodings {
+        return E::ENCODING
+    }
+    /// Gets the encoded string
+    pub fn as_str(&self) -> &str {
+        return &self.encoded
+    }
+    /// Gets the decoded bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        return &self.decoded
+    }
+    /// Decodes into a `Vec<u8>` (cannot fail, the string was validated)
+    pub fn decode(&self) -> Vec<u8> {
+        return self.decoded.clone()
+    }
+    /// Consumes it, returning the encoded string
+    pub fn into_string(mut self) -> String {
+        return core::mem::take(&mut self.encoded)
+    }
+}
+
+impl<E: SlugEncodingKind> Clone for Encoded<E> {
+    fn clone(&self) -> Self {
+        return Self { encoded: self.encoded.clone(), decoded: self.decoded.clone(), kind: PhantomData }
+    }
+}
+
+impl<E: SlugEncodingKind> Drop for Encoded<E> {
+    fn drop(&mut self) {
+        wipe(&mut self.decoded);
+    }
+}
+
+impl<E: SlugEncodingKind> ConstantTimeEq for Encoded<E> {
+    /// Compares the decoded bytes in constant-time (only the lengths are compared in variable time)
+    fn ct_eq(&self, other: &Self) -> Choice {
+        return self.decoded.as_slice().ct_eq(other.decoded.as_slice())
+    }
+}
+
+impl<E: SlugEncodingKind> PartialEq for Encoded<E> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.ct_eq(other).into()
+    }
+}
+
+impl<E: SlugEncodingKind> Eq for Encoded<E> {}
+
+impl<E: SlugEncodingKind> Hash for Encoded<E> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.decoded.hash(state);
+    }
+}
+
+impl<E: SlugEncodingKind> fmt::Debug for Encoded<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "Encoded<{:?}>({:?})", E::ENCODING, self.encoded)
+    }
+}
+
+impl<E: SlugEncodingKind> fmt::Display for Encoded<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str(&self.encoded)
+    }
+}
+
+impl<E: SlugEncodingKind> FromStr for Encoded<E> {
+    type Err = SlugEncodingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return Self::new(s)
+    }
+}
+
+impl<E: SlugEncodingKind> TryFrom<String> for Encoded<E> {
+    type Error = SlugEncodingError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        return Self::new(s)
+    }
+}
+
+impl<E: SlugEncodingKind> TryFrom<&str> for Encoded<E> {
+    type Error = SlugEncodingError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        return Self::new(s)
+    }
+}
+
+impl<E: SlugEncodingKind> AsRef<str> for Encoded<E> {
+    fn as_ref(&self) -> &str {
+        return &self.encoded
+    }
+}
+
+impl<E: SlugEncodingKind> From<Encoded<E>> for String {
+    fn from(encoded: Encoded<E>) -> Self {
+        return encoded.into_string()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: SlugEncodingKind> serde::Serialize for Encoded<E> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(&self.encoded)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, E: SlugEncodingKind> serde::Deserialize<'de> for Encoded<E> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EncodedVisitor<E>(PhantomData<fn() -> E>);
+
+        impl<E: SlugEncodingKind> serde::de::Visitor<'_> for EncodedVisitor<E> {
+            type Value = Encoded<E>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                return write!(f, "a {:?} string", E::ENCODING)
+            }
+            fn visit_str<Er: serde::de::Error>(self, v: &str) -> Result<Encoded<E>, Er> {
+                return Encoded::new(v).map_err(Er::custom)
+            }
+            fn visit_string<Er: serde::de::Error>(self, v: String) -> Result<Encoded<E>, Er> {
+                return Encoded::new(v).map_err(Er::custom)
+            }
+        }
+
+        return deserializer.deserialize_string(EncodedVisitor(PhantomData))
+    }
+}
+
+#[test]
+fn encoded_strings() {
+    let key = Base58String::new("2NEpo7TZRRrLZSi2U").unwrap();
+    assert_eq!(key.as_bytes(), b"Hello World!");
+    assert_eq!(key.get_encoding(), SlugEncodings::Base58);
+    assert_eq!(key.to_string(), "2NEpo7TZRRrLZSi2U");
+
+    // Equal bytes are equal however they are written
+    let upper: HexString = "DEADBEEF".parse().unwrap();
+    let lower = HexString::try_from("deadbeef").unwrap();
+    assert_eq!(upper, lower);
+    assert_eq!(upper.as_str(), "DEADBEEF");
+    assert!(bool::from(upper.ct_eq(&HexString::from_bytes([0xde, 0xad, 0xbe, 0xef]).unwrap())));
+    assert_ne!(upper, HexString::from_bytes([0xde, 0xad, 0xbe]).unwrap());
+    assert_ne!(upper, HexString::from_bytes([0xde, 0xad, 0xbe, 0xee]).unwrap());
+
+    let e = "c2x1Z2V*".parse::<Base64String>().unwrap_err();
+    assert_eq!((e.position(), e.character()), (Some(7), Some('*')));
+    assert!(Base64UrlString::new("-_8=").is_ok());
+    assert!(Base32String::new("mzxw6==").is_err());
+    assert!(Encoded::<Z85>::from_bytes([1, 2, 3]).is_err());
+    assert_eq!(String::from(Encoded::<Z85>::from_bytes([1, 2, 3, 4]).unwrap()), "0rJua");
+    assert_eq!(Encoded::<Bech32m>::new("a1lqfn3a").unwrap().as_bytes(), []);
+    assert!(Encoded::<Bech32>::from_bytes([1, 2, 3]).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn encoded_serde() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    assert_tokens(&HexString::new("dead").unwrap(), &[Token::Str("dead")]);
+    assert_de_tokens_error::<HexString>(&[Token::Str("dex")], "invalid character 'x' at byte 2 in Hex input");
+}
@@ -0,0 +1,240 @@
+//! # SlugEncodings (Names and Metadata)
+//!
+//! `SlugEncodings` has a canonical name (`Display`) and can be parsed from it or a common alias (`FromStr`, case-insensitive, ignoring `-` and `_`), so it can be stored in configuration files. With the `serde` feature it serializes as its canonical name.
+//!
+//! | Encoding | Name | Aliases |
+//! |----------|------|---------|
+//! | `Hex` | `hex` | `base16`, `b16` |
+//! | `Base32` | `base32` | `b32` |
+//! | `Base32unpadded` | `base32-unpadded` | `base32-nopad`, `b32-unpadded` |
+//! | `Base58` | `base58` | `b58` |
+//! | `Base58fast` | `base58-fast` | `b58-fast` |
+//! | `Base58monero` | `base58-monero` | `monero`, `b58-monero` |
+//! | `Base64` | `base64` | `b64` |
+//! | `Base64urlsafe` | `base64url` | `base64-urlsafe`, `b64url` |
+//! | `Ascii85` | `ascii85` | `a85` |
+//! | `Z85` | `z85` | |
+//! | `Base85rfc1924` | `base85-rfc1924` | `rfc1924`, `base85`, `b85` |
+//! | `Bech32` | `bech32` | |
+//! | `Bech32m` | `bech32m` | |
+//!
+//! The metadata methods (`is_constant_time`, `alphabet`, `padding`, `bits_per_symbol`) let tools introspect an encoding.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::errors::SlugEncodingError;
+use crate::{base58, base85, bech32, SlugEncodings};
+
+/// Every encoding (in declaration order).
+const ALL: [SlugEncodings;13] = [
+    SlugEncodings::Hex,
+    SlugEncodings::Base32,
+    SlugEncodings::Base32unpadded,
+    SlugEncodings::Base58,
+    SlugEncodings::Base58fast,
+    SlugEncodings::Base58monero,
+    SlugEncodings::Base64,
+    SlugEncodings::Base64urlsafe,
+    SlugEncodings::Ascii85,
+    SlugEncodings::Z85,
+    SlugEncodings::Base85rfc1924,
+    SlugEncodings::Bech32,
+    SlugEncodings::Bech32m,
+];
+
+impl SlugEncodings {
+    /// Gets every encoding
+    pub fn all() -> &'static [SlugEncodings] {
+        return &ALL
+    }
+    /// Gets the canonical name (used by `Display` and serde)
+    pub fn name(&self) -> &'static str {
+        match self {
+            SlugEncodings::Hex => "hex",
+            SlugEncodings::Base32 => "base32",
+            SlugEncodings::Base32unpadded => "base32-unpadded",
+            SlugEncodings::Base58 => "base58",
+            SlugEncodings::Base58fast => "base58-fast",
+            SlugEncodings::Base58monero => "base58-monero",
+            SlugEncodings::Base64 => "base64",
+            SlugEncodings::Base64urlsafe => "base64url",
+            SlugEncodings::Ascii85 => "ascii85",
+            SlugEncodings::Z85 => "z85",
+            SlugEncodings::Base85rfc1924 => "base85-rfc1924",
+            SlugEncodings::Bech32 => "bech32",
+            SlugEncodings::Bech32m => "bech32m",
+        }
+    }
+    /// Gets the aliases accepted by `FromStr` (besides the canonical name)
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            SlugEncodings::Hex => &["base16", "b16"],
+            SlugEncodings::Base32 => &["b32"],
+            SlugEncodings::Base32unpadded => &["base32-nopad", "b32-unpadded"],
+            SlugEncodings::Base58 => &["b58"],
+            SlugEncodings::Base58fast => &["b58-fast"],
+            SlugEncodings::Base58monero => &["monero", "b58-monero"],
+            SlugEncodings::Base64 => &["b64"],
+            SlugEncodings::Base64urlsafe => &["base64-urlsafe", "b64url"],
+            SlugEncodings::Ascii85 => &["a85"],
+            SlugEncodings::Z85 | SlugEncodings::Bech32 | SlugEncodings::Bech32m => &[],
+            SlugEncodings::Base85rfc1924 => &["rfc1924", "base85", "b85"],
+        }
+    }
+    /// Whether encoding and decoding run in constant-time
+    pub fn is_constant_time(&self) -> bool {
+        match self {
+            SlugEncodings::Hex |
+            SlugEncodings::Base32 |
+            SlugEncodings::Base32unpadded |
+            SlugEncodings::Base58 |
+            SlugEncodings::Base58monero |
+            SlugEncodings::Base64 |
+            SlugEncodings::Base64urlsafe => true,
+            SlugEncodings::Base58fast |
+            SlugEncodings::Ascii85 |
+            SlugEncodings::Z85 |
+            SlugEncodings::Base85rfc1924 |
+            SlugEncodings::Bech32 |
+            SlugEncodings::Bech32m => false,
+        }
+    }
+    /// Gets the alphabet used when encoding (in digit order, excluding the Bech32 prefix and separator)
+    pub fn alphabet(&self) -> &'static str {
+        let alphabet: &'static [u8] = match self {
+            SlugEncodings::Hex => b"0123456789abcdef",
+            SlugEncodings::Base32 | SlugEncodings::Base32unpadded => b"abcdefghijklmnopqrstuvwxyz234567",
+            SlugEncodings::Base58 | SlugEncodings::Base58fast | SlugEncodings::Base58monero => base58::BITCOIN_ALPHABET,
+            SlugEncodings::Base64 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+            SlugEncodings::Base64urlsafe => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_",
+            SlugEncodings::Ascii85 => base85::ASCII85_ALPHABET,
+            SlugEncodings::Z85 => base85::Z85_ALPHABET,
+            SlugEncodings::Base85rfc1924 => base85::RFC1924_ALPHABET,
+            SlugEncodings::Bech32 | SlugEncodings::Bech32m => bech32::CHARSET,
+        };
+        return core::str::from_utf8(alphabet).unwrap_or_default()
+    }
+    /// Gets the padding character (if any)
+    pub fn padding(&self) -> Option<char> {
+        match self {
+            SlugEncodings::Base32 | SlugEncodings::Base64 | SlugEncodings::Base64urlsafe => Some('='),
+            _ => None,
+        }
+    }
+    /// Gets the number of bits each character encodes (`log2` of the alphabet size)
+    pub fn bits_per_symbol(&self) -> f64 {
+        match self {
+            SlugEncodings::Hex => 4.0,
+            SlugEncodings::Base32 | SlugEncodings::Base32unpadded | SlugEncodings::Bech32 | SlugEncodings::Bech32m => 5.0,
+            SlugEncodings::Base58 | SlugEncodings::Base58fast | SlugEncodings::Base58monero => 5.857980995127572,
+            SlugEncodings::Base64 | SlugEncodings::Base64urlsafe => 6.0,
+            SlugEncodings::Ascii85 | SlugEncodings::Z85 | SlugEncodings::Base85rfc1924 => 6.409390936137702,
+        }
+    }
+}
+
+impl fmt::Display for SlugEncodings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return f.write_str(self.name())
+    }
+}
+
+impl FromStr for SlugEncodings {
+    type Err = SlugEncodingError;
+
+    /// Parses a canonical name or alias (case-insensitive, ignoring `-` and `_`), returning `SlugEncodingError::UnknownEncoding` otherwise.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return ALL.iter()
+            .copied()
+            .find(|encoding| name_matches(s, encoding.name()) || encoding.aliases().iter().any(|alias| name_matches(s, alias)))
+            .ok_or(SlugEncodingError::UnknownEncoding)
+    }
+}
+
+/// Compares names case-insensitively, ignoring `-` and `_`.
+fn name_matches(input: &str, name: &str) -> bool {
+    return normalize(input).eq(normalize(name))
+}
+
+fn normalize(s: &str) -> impl Iterator<Item = u8> + '_ {
+    return s.bytes().filter(|b| *b != b'-' && *b != b'_').map(|b| b.to_ascii_lowercase())
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SlugEncodings {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SlugEncodings {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct NameVisitor;
+
+        impl serde::de::Visitor<'_> for NameVisitor {
+            type Value = SlugEncodings;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                return write!(f, "the name of an encoding")
+            }
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<SlugEncodings, E> {
+                return v.parse().map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+
+        return deserializer.deserialize_str(NameVisitor)
+    }
+}
+
+#[test]
+fn encoding_names() {
+    assert_eq!(SlugEncodings::all().len(), 13);
+
+    for &encoding in SlugEncodings::all() {
+        assert_eq!(encoding.to_string().parse::<SlugEncodings>(), Ok(encoding));
+        assert_eq!(encoding.name().to_uppercase().parse::<SlugEncodings>(), Ok(encoding));
+
+        for alias in encoding.aliases() {
+            assert_eq!(alias.parse::<SlugEncodings>(), Ok(encoding));
+        }
+        assert_eq!(encoding.alphabet().len() as f64, encoding.bits_per_symbol().exp2().round());
+    }
+
+    assert_eq!("Base64_URL".parse::<SlugEncodings>(), Ok(SlugEncodings::Base64urlsafe));
+    assert_eq!("base16".parse::<SlugEncodings>(), Ok(SlugEncodings::Hex));
+    assert_eq!("base65".parse::<SlugEncodings>(), Err(SlugEncodingError::UnknownEncoding));
+    assert_eq!(SlugEncodings::Base64urlsafe.to_string(), "base64url");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn encoding_metadata() {
+    let bytes = [0xffu8, 0x00, 0x7f, 0x80, 0x01, 0xfe, 0x3c, 0xc3];
+
+    // Every character an encoding produces is in its alphabet (or is padding)
+    for usage in crate::test_usages() {
+        let (encoded, encoding) = (usage.encode(bytes).unwrap(), usage.get_encoding());
+        let encoded = encoded.trim_start_matches("<~").trim_end_matches("~>").trim_start_matches("test1");
+
+        assert!(encoded.chars().all(|c| encoding.alphabet().contains(c) || Some(c) == encoding.padding()), "{}", encoding);
+    }
+
+    assert!(SlugEncodings::Base58.is_constant_time());
+    assert!(!SlugEncodings::Base58fast.is_constant_time());
+    assert!(SlugEncodings::Base58monero.is_constant_time());
+    assert_eq!(SlugEncodings::Base32unpadded.padding(), None);
+    assert_eq!("BECH32M".parse::<SlugEncodings>(), Ok(SlugEncodings::Bech32m));
+    assert_eq!("monero".parse::<SlugEncodings>(), Ok(SlugEncodings::Base58monero));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn encoding_serde() {
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+    assert_tokens(&SlugEncodings::Base58fast, &[Token::Str("base58-fast")]);
+    assert_de_tokens(&SlugEncodings::Hex, &[Token::Str("Base16")]);
+    assert_de_tokens_error::<SlugEncodings>(&[Token::Str("base65")], "invalid value: string \"base65\", expected the name of an encoding");
+}
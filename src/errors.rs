@@ -1,7 +1,401 @@
+//! # Errors
+//!
+//! `SlugEncodingError` reports the encoding involved, the byte offset and offending character of invalid input, and padding/length problems. When a backend (`ct_codecs`, `base32ct` or `bs58`) reports the error, it is kept as the `source()`.
+//!
+//! **Note:** Errors are only diagnosed once decoding has failed, so building the error is not constant-time (the input was already invalid).
+
+use core::fmt;
+
+use crate::SlugEncodings;
+
+/// # SlugBackendError
+///
+/// The original error reported by the backend crate.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum SlugBackendError {
+    /// `ct_codecs` (Hex, Base64)
+    CtCodecs(ct_codecs::Error),
+    /// `base32ct` (Base32)
+    Base32(base32ct::Error),
+    /// `bs58` decoding (Base58 Fast)
+    Base58Decode(bs58::decode::Error),
+    /// `bs58` encoding (Base58 Fast)
+    Base58Encode(bs58::encode::Error),
+}
+
+impl fmt::Display for SlugBackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlugBackendError::CtCodecs(e) => write!(f, "ct_codecs: {}", e),
+            SlugBackendError::Base32(e) => write!(f, "base32ct: {}", e),
+            SlugBackendError::Base58Decode(e) => write!(f, "bs58: {}", e),
+            SlugBackendError::Base58Encode(e) => write!(f, "bs58: {}", e),
+        }
+    }
+}
+
+impl core::error::Error for SlugBackendError {}
+
 /// SlugErrors
-#[derive(Clone,Copy,Debug,PartialEq,PartialOrd,Hash)]
+#[derive(Clone,Debug,PartialEq,Eq)]
 pub enum SlugEncodingError {
-    Failed,
-    EncodingError,
-    DecodingError,
-}
\ No newline at end of file
+    /// Encoding failed
+    EncodingError {
+        encoding: SlugEncodings,
+        source: Option<SlugBackendError>,
+    },
+    /// Decoding failed without a more specific reason (e.g. non-canonical trailing bits)
+    DecodingError {
+        encoding: SlugEncodings,
+        source: Option<SlugBackendError>,
+    },
+    /// A character that is not part of the alphabet (`index` is the byte offset in the input)
+    InvalidCharacter {
+        encoding: SlugEncodings,
+        index: usize,
+        character: char,
+        source: Option<SlugBackendError>,
+    },
+    /// Missing, misplaced or too much padding (or an unterminated Ascii85 delimiter)
+    InvalidPadding {
+        encoding: SlugEncodings,
+        source: Option<SlugBackendError>,
+    },
+    /// The input length is not valid for the encoding
+    InvalidLength {
+        encoding: SlugEncodings,
+        length: usize,
+        source: Option<SlugBackendError>,
+    },
+    /// The input decodes to `actual` bytes instead of the `expected` size
+    LengthMismatch {
+        encoding: SlugEncodings,
+        expected: usize,
+        actual: usize,
+    },
+    /// The input (`length` bytes) is too long to decode to the `expected` size
+    InputTooLong {
+        encoding: SlugEncodings,
+        expected: usize,
+        length: usize,
+    },
+    /// The output buffer is smaller than `required`
+    BufferTooSmall {
+        encoding: SlugEncodings,
+        required: usize,
+    },
+    /// A group decodes to a value that does not fit (e.g. Base85 groups above `u32::MAX`)
+    Overflow {
+        encoding: SlugEncodings,
+    },
+    /// The encoding cannot be processed in blocks (Base58, Ascii85 and Bech32), so there is no streaming encoder/decoder for it
+    StreamingUnsupported {
+        encoding: SlugEncodings,
+    },
+    /// An integer has more than `max` bytes
+    IntegerOverflow {
+        length: usize,
+        max: usize,
+    },
+    /// The input does not decode with any detectable encoding (or is not the name of one)
+    UnknownEncoding,
+    /// The input decodes with more than one encoding with similar confidence
+    AmbiguousEncoding,
+    /// The checksum does not match the payload (or there is no room for one)
+    ChecksumMismatch,
+    /// The Bech32 human-readable prefix is missing, too long, not printable ASCII, mixed-case or not the expected one
+    InvalidHrp {
+        encoding: SlugEncodings,
+    },
+    /// The checksum built into the encoding (Bech32, Base58Check) does not match (`index` is the byte offset of the wrong character when a single substitution explains it)
+    InvalidChecksum {
+        encoding: SlugEncodings,
+        index: Option<usize>,
+    },
+    /// The Base58Check version bytes are not the expected (or a well-known) version
+    InvalidVersion {
+        encoding: SlugEncodings,
+    },
+    /// A user-defined alphabet is not valid (`index` is the first repeated or unprintable symbol, `None` if the length is wrong)
+    InvalidAlphabet {
+        encoding: SlugEncodings,
+        index: Option<usize>,
+    },
+    /// A backend error converted with `From` (without the encoding or input to diagnose it)
+    Backend(SlugBackendError),
+}
+
+impl SlugEncodingError {
+    /// Gets the encoding involved (if any)
+    pub fn encoding(&self) -> Option<SlugEncodings> {
+        match *self {
+            SlugEncodingError::EncodingError { encoding, .. } |
+            SlugEncodingError::DecodingError { encoding, .. } |
+            SlugEncodingError::InvalidCharacter { encoding, .. } |
+            SlugEncodingError::InvalidPadding { encoding, .. } |
+            SlugEncodingError::InvalidLength { encoding, .. } |
+            SlugEncodingError::LengthMismatch { encoding, .. } |
+            SlugEncodingError::InputTooLong { encoding, .. } |
+            SlugEncodingError::BufferTooSmall { encoding, .. } |
+            SlugEncodingError::Overflow { encoding } |
+            SlugEncodingError::StreamingUnsupported { encoding } |
+            SlugEncodingError::InvalidHrp { encoding } |
+            SlugEncodingError::InvalidChecksum { encoding, .. } |
+            SlugEncodingError::InvalidVersion { encoding } |
+            SlugEncodingError::InvalidAlphabet { encoding, .. } => Some(encoding),
+            SlugEncodingError::IntegerOverflow { .. } |
+            SlugEncodingError::Backend(_) |
+            SlugEncodingError::ChecksumMismatch |
+            SlugEncodingError::UnknownEncoding |
+            SlugEncodingError::AmbiguousEncoding => None,
+        }
+    }
+    /// Gets the byte offset of the offending character (if any)
+    pub fn position(&self) -> Option<usize> {
+        match *self {
+            SlugEncodingError::InvalidCharacter { index, .. } => Some(index),
+            SlugEncodingError::InvalidChecksum { index, .. } => index,
+            _ => None,
+        }
+    }
+    /// Gets the offending character (if any)
+    pub fn character(&self) -> Option<char> {
+        match *self {
+            SlugEncodingError::InvalidCharacter { character, .. } => Some(character),
+            _ => None,
+        }
+    }
+    /// Gets the backend error (if any)
+    pub fn backend(&self) -> Option<&SlugBackendError> {
+        match self {
+            SlugEncodingError::EncodingError { source, .. } |
+            SlugEncodingError::DecodingError { source, .. } |
+            SlugEncodingError::InvalidCharacter { source, .. } |
+            SlugEncodingError::InvalidPadding { source, .. } |
+            SlugEncodingError::InvalidLength { source, .. } => source.as_ref(),
+            SlugEncodingError::Backend(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SlugEncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlugEncodingError::EncodingError { encoding, .. } => write!(f, "failed to encode {:?}", encoding),
+            SlugEncodingError::DecodingError { encoding, .. } => write!(f, "failed to decode {:?}", encoding),
+            SlugEncodingError::InvalidCharacter { encoding, index, character, .. } => write!(f, "invalid character {:?} at byte {} in {:?} input", character, index, encoding),
+            SlugEncodingError::InvalidPadding { encoding, .. } => write!(f, "invalid padding in {:?} input", encoding),
+            SlugEncodingError::InvalidLength { encoding, length, .. } => write!(f, "invalid length {} for {:?} input", length, encoding),
+            SlugEncodingError::LengthMismatch { encoding, expected, actual } => write!(f, "{:?} input decodes to {} bytes (expected {})", encoding, actual, expected),
+            SlugEncodingError::InputTooLong { encoding, expected, length } => write!(f, "{:?} input of {} bytes is too long to decode to {} bytes", encoding, length, expected),
+            SlugEncodingError::BufferTooSmall { encoding, required } => write!(f, "buffer too small for {:?} (requires {} bytes)", encoding, required),
+            SlugEncodingError::Overflow { encoding } => write!(f, "{:?} group overflows", encoding),
+            SlugEncodingError::StreamingUnsupported { encoding } => write!(f, "{:?} cannot be streamed (it is not encoded in fixed-size blocks)", encoding),
+            SlugEncodingError::IntegerOverflow { length, max } => write!(f, "integer of {} bytes does not fit in {} bytes", length, max),
+            SlugEncodingError::UnknownEncoding => write!(f, "input does not match any encoding"),
+            SlugEncodingError::AmbiguousEncoding => write!(f, "input matches more than one encoding"),
+            SlugEncodingError::ChecksumMismatch => write!(f, "checksum mismatch"),
+            SlugEncodingError::InvalidHrp { encoding } => write!(f, "invalid human-readable prefix in {:?} input", encoding),
+            SlugEncodingError::InvalidChecksum { encoding, index: Some(index) } => write!(f, "invalid {:?} checksum (likely error at byte {})", encoding, index),
+            SlugEncodingError::InvalidChecksum { encoding, index: None } => write!(f, "invalid {:?} checksum", encoding),
+            SlugEncodingError::InvalidVersion { encoding } => write!(f, "unexpected version bytes in {:?} input", encoding),
+            SlugEncodingError::InvalidAlphabet { encoding, index: Some(index) } => write!(f, "invalid {:?} alphabet (repeated or unprintable symbol at byte {})", encoding, index),
+            SlugEncodingError::InvalidAlphabet { encoding, index: None } => write!(f, "invalid {:?} alphabet (wrong length)", encoding),
+            SlugEncodingError::Backend(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl core::error::Error for SlugEncodingError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            SlugEncodingError::EncodingError { source, .. } |
+            SlugEncodingError::DecodingError { source, .. } |
+            SlugEncodingError::InvalidCharacter { source, .. } |
+            SlugEncodingError::InvalidPadding { source, .. } |
+            SlugEncodingError::InvalidLength { source, .. } => source.as_ref().map(|e| e as &(dyn core::error::Error + 'static)),
+            SlugEncodingError::Backend(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Builds an `InvalidCharacter` error for the (possibly multi-byte) character at `index`.
+pub(crate) fn invalid_character(encoding: SlugEncodings, input: &[u8], index: usize, source: Option<SlugBackendError>) -> SlugEncodingError {
+    let end = input.len().min(index + 4);
+    let character = match core::str::from_utf8(&input[index..end]) {
+        Ok(s) => s.chars().next(),
+        Err(e) => core::str::from_utf8(&input[index..index + e.valid_up_to()]).ok().and_then(|s| s.chars().next()),
+    };
+
+    SlugEncodingError::InvalidCharacter {
+        encoding,
+        index,
+        character: character.unwrap_or(char::REPLACEMENT_CHARACTER),
+        source,
+    }
+}
+
+/// # Diagnose
+///
+/// Works out why `input` failed to decode with `encoding` (the first invalid character, then padding, then length).
+pub(crate) fn diagnose(encoding: SlugEncodings, input: &[u8], source: Option<SlugBackendError>) -> SlugEncodingError {
+    let padding = match encoding {
+        SlugEncodings::Base32 | SlugEncodings::Base64 | SlugEncodings::Base64urlsafe => Some(b'='),
+        _ => None,
+    };
+
+    if let Some(index) = input.iter().position(|&c| !is_symbol(encoding, c) && Some(c) != padding) {
+        return invalid_character(encoding, input, index, source)
+    }
+
+    let unpadded = match padding {
+        Some(pad) => input.iter().rposition(|&c| c != pad).map_or(0, |i| i + 1),
+        None => input.len(),
+    };
+
+    // Padding in the middle of the input
+    if input[..unpadded].iter().any(|&c| Some(c) == padding) {
+        return SlugEncodingError::InvalidPadding { encoding, source }
+    }
+
+    let length = input.len();
+
+    match encoding {
+        SlugEncodings::Hex if !length.is_multiple_of(2) => SlugEncodingError::InvalidLength { encoding, length, source },
+        SlugEncodings::Base32 | SlugEncodings::Base32unpadded if matches!(unpadded % 8, 1 | 3 | 6) => SlugEncodingError::InvalidLength { encoding, length, source },
+        SlugEncodings::Base32 if !length.is_multiple_of(8) || length - unpadded > 6 => SlugEncodingError::InvalidPadding { encoding, source },
+        SlugEncodings::Base64 | SlugEncodings::Base64urlsafe if unpadded % 4 == 1 => SlugEncodingError::InvalidLength { encoding, length, source },
+        SlugEncodings::Base64 | SlugEncodings::Base64urlsafe if !length.is_multiple_of(4) || length - unpadded > 2 => SlugEncodingError::InvalidPadding { encoding, source },
+        SlugEncodings::Z85 if !length.is_multiple_of(5) => SlugEncodingError::InvalidLength { encoding, length, source },
+        SlugEncodings::Base85rfc1924 if length % 5 == 1 => SlugEncodingError::InvalidLength { encoding, length, source },
+        SlugEncodings::Z85 | SlugEncodings::Base85rfc1924 | SlugEncodings::Ascii85 => SlugEncodingError::Overflow { encoding },
+        _ => SlugEncodingError::DecodingError { encoding, source },
+    }
+}
+
+/// Returns a `map_err` closure that wraps a backend error in `EncodingError`.
+pub(crate) fn encoding_error<E: Into<SlugBackendError>>(encoding: SlugEncodings) -> impl FnOnce(E) -> SlugEncodingError {
+    move |e| SlugEncodingError::EncodingError { encoding, source: Some(e.into()) }
+}
+
+/// Returns a `map_err` closure that diagnoses a backend error for `input`.
+pub(crate) fn decoding_error<E: Into<SlugBackendError>>(encoding: SlugEncodings, input: &[u8]) -> impl FnOnce(E) -> SlugEncodingError + '_ {
+    move |e| diagnose(encoding, input, Some(e.into()))
+}
+
+/// Returns a `map_err` closure for `bs58` decoding errors, which carry the offending byte offset themselves (the alphabet may not be Bitcoin's, so `diagnose` cannot find it).
+pub(crate) fn bs58_decoding_error(input: &[u8]) -> impl FnOnce(bs58::decode::Error) -> SlugEncodingError + '_ {
+    move |e| match e {
+        bs58::decode::Error::InvalidCharacter { index, .. } |
+        bs58::decode::Error::NonAsciiCharacter { index } => invalid_character(SlugEncodings::Base58fast, input, index, Some(e.into())),
+        _ => diagnose(SlugEncodings::Base58fast, input, Some(e.into())),
+    }
+}
+
+/// Returns true if `c` is part of the alphabet of `encoding` (excluding padding).
+fn is_symbol(encoding: SlugEncodings, c: u8) -> bool {
+    match encoding {
+        SlugEncodings::Hex => c.is_ascii_hexdigit(),
+        SlugEncodings::Base32 | SlugEncodings::Base32unpadded => matches!(c, b'a'..=b'z' | b'2'..=b'7'),
+        SlugEncodings::Base58 | SlugEncodings::Base58fast | SlugEncodings::Base58monero => crate::base58::BITCOIN_ALPHABET.contains(&c),
+        SlugEncodings::Base64 => c.is_ascii_alphanumeric() || c == b'+' || c == b'/',
+        SlugEncodings::Base64urlsafe => c.is_ascii_alphanumeric() || c == b'-' || c == b'_',
+        SlugEncodings::Ascii85 => matches!(c, b'!'..=b'u' | b'z' | b'~') || c.is_ascii_whitespace(),
+        SlugEncodings::Z85 => crate::base85::Z85_ALPHABET.contains(&c),
+        SlugEncodings::Base85rfc1924 => crate::base85::RFC1924_ALPHABET.contains(&c),
+        SlugEncodings::Bech32 | SlugEncodings::Bech32m => (33..=126).contains(&c),
+    }
+}
+
+impl From<ct_codecs::Error> for SlugBackendError {
+    fn from(e: ct_codecs::Error) -> Self {
+        SlugBackendError::CtCodecs(e)
+    }
+}
+
+impl From<base32ct::Error> for SlugBackendError {
+    fn from(e: base32ct::Error) -> Self {
+        SlugBackendError::Base32(e)
+    }
+}
+
+impl From<bs58::decode::Error> for SlugBackendError {
+    fn from(e: bs58::decode::Error) -> Self {
+        SlugBackendError::Base58Decode(e)
+    }
+}
+
+impl From<bs58::encode::Error> for SlugBackendError {
+    fn from(e: bs58::encode::Error) -> Self {
+        SlugBackendError::Base58Encode(e)
+    }
+}
+
+impl From<SlugBackendError> for SlugEncodingError {
+    fn from(e: SlugBackendError) -> Self {
+        SlugEncodingError::Backend(e)
+    }
+}
+
+impl From<ct_codecs::Error> for SlugEncodingError {
+    fn from(e: ct_codecs::Error) -> Self {
+        SlugEncodingError::Backend(e.into())
+    }
+}
+
+impl From<base32ct::Error> for SlugEncodingError {
+    fn from(e: base32ct::Error) -> Self {
+        SlugEncodingError::Backend(e.into())
+    }
+}
+
+impl From<bs58::decode::Error> for SlugEncodingError {
+    fn from(e: bs58::decode::Error) -> Self {
+        SlugEncodingError::Backend(e.into())
+    }
+}
+
+impl From<bs58::encode::Error> for SlugEncodingError {
+    fn from(e: bs58::encode::Error) -> Self {
+        SlugEncodingError::Backend(e.into())
+    }
+}
+
+#[test]
+fn diagnose_errors() {
+    let e = diagnose(SlugEncodings::Hex, "abzd".as_bytes(), None);
+    assert_eq!((e.position(), e.character()), (Some(2), Some('z')));
+
+    let e = diagnose(SlugEncodings::Base58, "1Ffmé".as_bytes(), None);
+    assert_eq!((e.position(), e.character()), (Some(4), Some('é')));
+
+    assert!(matches!(diagnose(SlugEncodings::Hex, b"abc", None), SlugEncodingError::InvalidLength { length: 3, .. }));
+    assert!(matches!(diagnose(SlugEncodings::Base64, b"ab=c", None), SlugEncodingError::InvalidPadding { .. }));
+    assert!(matches!(diagnose(SlugEncodings::Base64, b"abc", None), SlugEncodingError::InvalidPadding { .. }));
+    assert!(matches!(diagnose(SlugEncodings::Base32, b"aaaaaa==", None), SlugEncodingError::InvalidLength { .. }));
+
+    let e = SlugEncodingError::from(ct_codecs::Error::InvalidInput);
+    assert_eq!(e.backend(), Some(&SlugBackendError::CtCodecs(ct_codecs::Error::InvalidInput)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn decode_errors() {
+    use std::error::Error as _;
+    use crate::{SlugDecoder, SlugEncodingUsage};
+
+    let e = SlugEncodingUsage::new(SlugEncodings::Base64).decode("SGVsbG8*").unwrap_err();
+    assert_eq!((e.encoding(), e.position(), e.character()), (Some(SlugEncodings::Base64), Some(7), Some('*')));
+    assert_eq!(e.to_string(), "invalid character '*' at byte 7 in Base64 input");
+    assert!(e.source().is_some());
+
+    let e = SlugEncodingUsage::new(SlugEncodings::Hex).decode("abc").unwrap_err();
+    assert!(matches!(e, SlugEncodingError::InvalidLength { length: 3, .. }));
+
+    let e = SlugEncodingUsage::new(SlugEncodings::Base58fast).decode("3mJr0").unwrap_err();
+    assert_eq!((e.position(), e.character()), (Some(4), Some('0')));
+    let e = "3mJr0".from_base58_fast().unwrap_err();
+    assert_eq!((e.encoding(), e.position(), e.character()), (Some(SlugEncodings::Base58fast), Some(4), Some('0')));
+}
@@ -0,0 +1,355 @@
+//! # Large Unsigned Integers
+//!
+//! Encodes unsigned integers (`u8` to `u128`, plus the fixed-size `U256` and `U512` provided by this crate) without any manual `to_be_bytes` shuffling.
+//!
+//! The byte order is always chosen explicitly with `SlugEndian`, and `SlugIntWidth` chooses between keeping every byte (`Fixed`) or stripping the most significant zero bytes (`Minimal`).
+//!
+//! `to_slug_bytes` returns `SlugIntBytes`, which implements `SlugEncoder` (so every `to_*` method works on it), and `SlugDecoder::decode_integer` decodes an integer from any encoded string. `to_encoded` and `from_encoded` take a `SlugEncodingUsage` (or bare `SlugEncodings`), so a Base58 alphabet or Bech32 prefix can be used.
+//!
+//! ## Example Code
+//!
+//! ```rust
+//! use slugencode::integers::{SlugInteger, SlugEndian, SlugIntWidth, U256};
+//! use slugencode::base58::Base58Alphabet;
+//! use slugencode::{SlugDecoder, SlugEncoder, SlugEncodingUsage, SlugEncodings};
+//!
+//! # #[cfg(feature = "alloc")]
+//! fn main() {
+//!     let nonce: u64 = 0x0102;
+//!
+//!     assert_eq!(nonce.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Fixed).to_hex().unwrap(), "0000000000000102");
+//!     assert_eq!(nonce.to_slug_bytes(SlugEndian::Little, SlugIntWidth::Minimal).to_hex().unwrap(), "0201");
+//!     assert_eq!("0201".decode_integer::<u64>(SlugEncodings::Hex, SlugEndian::Little).unwrap(), nonce);
+//!
+//!     let scalar = U256::from(0xffu8);
+//!     let usage = SlugEncodingUsage::new(SlugEncodings::Base58).with_alphabet(Base58Alphabet::RIPPLE);
+//!     let encoded = scalar.to_encoded(usage, SlugEndian::Big, SlugIntWidth::Fixed).unwrap();
+//!     assert_eq!(U256::from_encoded(&encoded, usage, SlugEndian::Big).unwrap(), scalar);
+//! }
+//! # #[cfg(not(feature = "alloc"))]
+//! # fn main() {}
+//! ```
+
+use core::ops::Deref;
+
+use crate::errors::SlugEncodingError;
+#[cfg(feature = "alloc")]
+use crate::SlugEncodingUsage;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// The largest integer supported (in bytes).
+pub const MAX_INT_BYTES: usize = 64;
+
+/// # SlugEndian
+///
+/// The byte order of an encoded integer.
+#[derive(Clone,Copy,Debug,PartialEq,PartialOrd,Hash)]
+pub enum SlugEndian {
+    Big,
+    Little,
+}
+
+/// # SlugIntWidth
+///
+/// - `Fixed`: Keeps the full width of the integer (leading zeros are kept).
+/// - `Minimal`: Strips the most significant zero bytes. Zero is encoded as a single zero byte.
+#[derive(Clone,Copy,Debug,PartialEq,PartialOrd,Hash)]
+pub enum SlugIntWidth {
+    Fixed,
+    Minimal,
+}
+
+/// # SlugIntBytes
+///
+/// The bytes of an integer, stored on the stack. Dereferences to `[u8]` and implements `SlugEncoder`, which is how an integer is encoded with a given byte order and width (e.g. `x.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Minimal).to_hex()`).
+#[derive(Clone,Copy,Debug,PartialEq,Eq,Hash)]
+pub struct SlugIntBytes {
+    bytes: [u8;MAX_INT_BYTES],
+    len: usize,
+}
+
+impl SlugIntBytes {
+    fn new(bytes: &[u8]) -> Self {
+        let mut output = Self { bytes: [0u8;MAX_INT_BYTES], len: bytes.len() };
+        output.bytes[..bytes.len()].copy_from_slice(bytes);
+        return output
+    }
+    /// Returns the bytes as a slice
+    pub fn as_slice(&self) -> &[u8] {
+        return &self.bytes[..self.len]
+    }
+}
+
+impl Deref for SlugIntBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        return self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for SlugIntBytes {
+    fn as_ref(&self) -> &[u8] {
+        return self.as_slice()
+    }
+}
+
+/// # SlugInteger
+///
+/// Implemented for `u8`, `u16`, `u32`, `u64`, `u128`, `U256` and `U512`.
+pub trait SlugInteger: Sized {
+    /// The width of the integer (in bytes)
+    const BYTES: usize;
+
+    /// Returns the full-width big-endian bytes
+    fn to_be_slug_bytes(&self) -> SlugIntBytes;
+    /// Creates the integer from exactly `Self::BYTES` big-endian bytes
+    fn from_be_slug_bytes(bytes: &[u8]) -> Self;
+
+    /// # To Bytes
+    ///
+    /// Returns the bytes in the given byte order and width.
+    fn to_slug_bytes(&self, endian: SlugEndian, width: SlugIntWidth) -> SlugIntBytes {
+        let be = self.to_be_slug_bytes();
+
+        let start = match width {
+            SlugIntWidth::Fixed => 0,
+            SlugIntWidth::Minimal => be.iter().position(|&b| b != 0).unwrap_or(Self::BYTES - 1),
+        };
+
+        let mut output = SlugIntBytes::new(&be[start..]);
+
+        if endian == SlugEndian::Little {
+            output.bytes[..output.len].reverse();
+        }
+        return output
+    }
+    /// # From Bytes
+    ///
+    /// Creates the integer from bytes in the given byte order. Accepts both fixed-width and minimal encodings, and more than `Self::BYTES` bytes as long as the extra most significant bytes are zero.
+    ///
+    /// Returns `SlugEncodingError::IntegerOverflow` if the value does not fit in `Self::BYTES` bytes.
+    fn from_slug_bytes(bytes: &[u8], endian: SlugEndian) -> Result<Self, SlugEncodingError> {
+        let split = bytes.len().saturating_sub(Self::BYTES);
+
+        let (excess, bytes) = match endian {
+            SlugEndian::Big => bytes.split_at(split),
+            SlugEndian::Little => {
+                let (bytes, excess) = bytes.split_at(bytes.len() - split);
+                (excess, bytes)
+            }
+        };
+
+        if excess.iter().any(|&b| b != 0) {
+            return Err(SlugEncodingError::IntegerOverflow { length: excess.len() + bytes.len(), max: Self::BYTES })
+        }
+
+        let mut be = [0u8;MAX_INT_BYTES];
+        let padded = &mut be[..Self::BYTES];
+        padded[Self::BYTES - bytes.len()..].copy_from_slice(bytes);
+
+        if endian == SlugEndian::Little {
+            padded[Self::BYTES - bytes.len()..].reverse();
+        }
+        return Ok(Self::from_be_slug_bytes(padded))
+    }
+    /// # To Encoded String
+    ///
+    /// Encodes the integer with the given usage (a `SlugEncodingUsage` or `SlugEncodings`), byte order and width.
+    #[cfg(feature = "alloc")]
+    fn to_encoded(&self, usage: impl Into<SlugEncodingUsage>, endian: SlugEndian, width: SlugIntWidth) -> Result<String, SlugEncodingError> {
+        return usage.into().encode(self.to_slug_bytes(endian, width))
+    }
+    /// # From Encoded String
+    ///
+    /// Decodes an integer encoded with the given usage (a `SlugEncodingUsage` or `SlugEncodings`) and byte order.
+    #[cfg(feature = "alloc")]
+    fn from_encoded<T: AsRef<[u8]>>(encoded_str: T, usage: impl Into<SlugEncodingUsage>, endian: SlugEndian) -> Result<Self, SlugEncodingError> {
+        let bytes = usage.into().decode(encoded_str)?;
+        return Self::from_slug_bytes(&bytes, endian)
+    }
+}
+
+macro_rules! impl_slug_integer_native {
+    ($($t:ty),*) => {
+        $(
+            impl SlugInteger for $t {
+                const BYTES: usize = core::mem::size_of::<$t>();
+
+                fn to_be_slug_bytes(&self) -> SlugIntBytes {
+                    return SlugIntBytes::new(&self.to_be_bytes())
+                }
+                fn from_be_slug_bytes(bytes: &[u8]) -> Self {
+                    let mut be = [0u8; core::mem::size_of::<$t>()];
+                    be.copy_from_slice(bytes);
+                    return <$t>::from_be_bytes(be)
+                }
+            }
+        )*
+    };
+}
+
+impl_slug_integer_native!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_slug_integer_large {
+    ($name:ident, $bytes:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Stored as big-endian bytes, so the derived ordering is the numeric ordering.
+        #[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Ord,Hash)]
+        pub struct $name([u8;$bytes]);
+
+        impl $name {
+            /// Zero
+            pub const ZERO: Self = Self([0u8;$bytes]);
+            /// The largest value
+            pub const MAX: Self = Self([0xffu8;$bytes]);
+
+            /// Creates the integer from big-endian bytes
+            pub const fn from_be_bytes(bytes: [u8;$bytes]) -> Self {
+                return Self(bytes)
+            }
+            /// Creates the integer from little-endian bytes
+            pub fn from_le_bytes(mut bytes: [u8;$bytes]) -> Self {
+                bytes.reverse();
+                return Self(bytes)
+            }
+            /// Returns the big-endian bytes
+            pub const fn to_be_bytes(&self) -> [u8;$bytes] {
+                return self.0
+            }
+            /// Returns the little-endian bytes
+            pub fn to_le_bytes(&self) -> [u8;$bytes] {
+                let mut bytes = self.0;
+                bytes.reverse();
+                return bytes
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                return Self::ZERO
+            }
+        }
+
+        impl SlugInteger for $name {
+            const BYTES: usize = $bytes;
+
+            fn to_be_slug_bytes(&self) -> SlugIntBytes {
+                return SlugIntBytes::new(&self.0)
+            }
+            fn from_be_slug_bytes(bytes: &[u8]) -> Self {
+                let mut be = [0u8;$bytes];
+                be.copy_from_slice(bytes);
+                return Self(be)
+            }
+        }
+
+        impl_slug_integer_large!(@from $name, $bytes, u8, u16, u32, u64, u128);
+    };
+    (@from $name:ident, $bytes:expr, $($t:ty),*) => {
+        $(
+            impl From<$t> for $name {
+                fn from(value: $t) -> Self {
+                    let mut be = [0u8;$bytes];
+                    be[$bytes - core::mem::size_of::<$t>()..].copy_from_slice(&value.to_be_bytes());
+                    return Self(be)
+                }
+            }
+        )*
+    };
+}
+
+impl_slug_integer_large!(U256, 32, "# U256\n\nA fixed 256-bit unsigned integer (e.g. curve scalars).");
+impl_slug_integer_large!(U512, 64, "# U512\n\nA fixed 512-bit unsigned integer.");
+
+#[test]
+fn integer_widths() {
+    let x: u32 = 0x00010203;
+
+    assert_eq!(x.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Fixed).as_slice(), [0, 1, 2, 3]);
+    assert_eq!(x.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Minimal).as_slice(), [1, 2, 3]);
+    assert_eq!(x.to_slug_bytes(SlugEndian::Little, SlugIntWidth::Fixed).as_slice(), [3, 2, 1, 0]);
+    assert_eq!(x.to_slug_bytes(SlugEndian::Little, SlugIntWidth::Minimal).as_slice(), [3, 2, 1]);
+    assert_eq!(0u64.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Minimal).as_slice(), [0]);
+
+    assert_eq!(u32::from_slug_bytes(&[1, 2, 3], SlugEndian::Big).unwrap(), x);
+    assert_eq!(u32::from_slug_bytes(&[3, 2, 1], SlugEndian::Little).unwrap(), x);
+    assert_eq!(u32::from_slug_bytes(&[3, 2, 1, 0], SlugEndian::Little).unwrap(), x);
+    assert!(u32::from_slug_bytes(&[1, 0, 1, 2, 3], SlugEndian::Big).is_err());
+    assert!(u32::from_slug_bytes(&[3, 2, 1, 0, 1], SlugEndian::Little).is_err());
+}
+
+#[test]
+fn integer_leading_zeros() {
+    // Extra most significant bytes are accepted when they are zero
+    assert_eq!(u128::from_slug_bytes(&[0u8;17], SlugEndian::Big), Ok(0));
+    assert_eq!(u32::from_slug_bytes(&[0, 0, 1, 2, 3], SlugEndian::Big), Ok(0x00010203));
+    assert_eq!(u32::from_slug_bytes(&[3, 2, 1, 0, 0, 0], SlugEndian::Little), Ok(0x00010203));
+    assert_eq!(u8::from_slug_bytes(&[0, 0, 1], SlugEndian::Little), Err(SlugEncodingError::IntegerOverflow { length: 3, max: 1 }));
+
+    let mut bytes = [0u8;33];
+    bytes[32] = 0xff;
+    assert_eq!(U256::from_slug_bytes(&bytes, SlugEndian::Big), Ok(U256::from(0xffu8)));
+    assert_eq!(U256::from_slug_bytes(&bytes, SlugEndian::Little), Err(SlugEncodingError::IntegerOverflow { length: 33, max: 32 }));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn integer_decoder() {
+    use crate::{SlugDecoder, SlugEncodings};
+
+    assert_eq!("0000000000000102".decode_integer::<u64>(SlugEncodings::Hex, SlugEndian::Big), Ok(0x0102));
+    assert_eq!("0201".decode_integer::<u64>(SlugEncodings::Hex, SlugEndian::Little), Ok(0x0102));
+    assert_eq!("000000000000000000000000000000000102".decode_integer::<u128>(SlugEncodings::Hex, SlugEndian::Big), Ok(0x0102));
+    assert!("010000".decode_integer::<u16>(SlugEncodings::Hex, SlugEndian::Big).is_err());
+
+    let encoded = U256::MAX.to_encoded(SlugEncodings::Base58, SlugEndian::Big, SlugIntWidth::Fixed).unwrap();
+    assert_eq!(encoded.decode_integer::<U256>(SlugEncodings::Base58, SlugEndian::Big), Ok(U256::MAX));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn integer_encoder() {
+    use crate::{SlugEncoder, SlugEncodings};
+
+    // SlugIntBytes implements SlugEncoder, so each `to_*` method encodes an integer
+    let x: u32 = 0x00010203;
+    assert_eq!(x.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Minimal).to_hex().unwrap(), "010203");
+    assert_eq!(x.to_slug_bytes(SlugEndian::Little, SlugIntWidth::Fixed).to_bs64().unwrap(), "AwIBAA==");
+    assert_eq!(U256::MAX.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Fixed).to_base58().unwrap(), U256::MAX.to_encoded(SlugEncodings::Base58, SlugEndian::Big, SlugIntWidth::Fixed).unwrap());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn integer_usages() {
+    use crate::base58::Base58Alphabet;
+    use crate::{SlugDecoder, SlugEncodings};
+
+    let ripple = SlugEncodingUsage::new(SlugEncodings::Base58).with_alphabet(Base58Alphabet::RIPPLE);
+    assert_eq!(0u32.to_encoded(ripple, SlugEndian::Big, SlugIntWidth::Fixed).unwrap(), "rrrr");
+    assert_eq!(u32::from_encoded("rrrr", ripple, SlugEndian::Big), Ok(0));
+    assert_eq!("rrrr".decode_integer::<u32>(ripple, SlugEndian::Big), Ok(0));
+
+    let age = SlugEncodingUsage::new(SlugEncodings::Bech32).with_hrp("age").unwrap();
+    let encoded = U256::MAX.to_encoded(age, SlugEndian::Little, SlugIntWidth::Fixed).unwrap();
+    assert!(encoded.starts_with("age1"));
+    assert_eq!(encoded.decode_integer::<U256>(age, SlugEndian::Little), Ok(U256::MAX));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn integer_large() {
+    use crate::SlugEncodings;
+
+    let x = U256::from(u128::MAX);
+    assert_eq!(x.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Minimal).as_slice(), [0xff; 16]);
+    assert_eq!(U256::from_le_bytes(x.to_le_bytes()), x);
+    assert!(U256::MAX > x);
+
+    let encoded = U512::MAX.to_encoded(SlugEncodings::Hex, SlugEndian::Little, SlugIntWidth::Fixed).unwrap();
+    assert_eq!(encoded, "ff".repeat(64));
+    assert_eq!(U512::from_encoded(encoded, SlugEncodings::Hex, SlugEndian::Little).unwrap(), U512::MAX);
+}
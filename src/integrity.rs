@@ -0,0 +1,84 @@
+//! # Integrity-Check (BLAKE2s)
+//!
+//! Requires the `integrity-check` feature.
+//!
+//! A checksum (the BLAKE2s-256 digest of the payload truncated to `CHECKSUM_LEN` bytes) is appended to the payload before it is encoded. On decode, the checksum is recomputed and compared in constant-time using `subtle`, so typos in copied keys are caught before they are used.
+
+use blake2::{Blake2s256, Digest};
+use subtle::ConstantTimeEq;
+
+use crate::errors::SlugEncodingError;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The length of the checksum appended to the payload (in bytes).
+pub const CHECKSUM_LEN: usize = 4;
+
+/// # Checksum
+///
+/// Returns the BLAKE2s-256 digest of `bytes` truncated to `CHECKSUM_LEN` bytes.
+pub fn checksum<T: AsRef<[u8]>>(bytes: T) -> [u8;CHECKSUM_LEN] {
+    let digest = Blake2s256::digest(bytes.as_ref());
+
+    let mut checksum = [0u8;CHECKSUM_LEN];
+    checksum.copy_from_slice(&digest[..CHECKSUM_LEN]);
+    checksum
+}
+
+/// # Append Checksum
+///
+/// Returns `bytes` followed by its checksum.
+#[cfg(feature = "alloc")]
+pub fn append_checksum<T: AsRef<[u8]>>(bytes: T) -> Vec<u8> {
+    let bytes = bytes.as_ref();
+
+    let mut checked = Vec::with_capacity(bytes.len() + CHECKSUM_LEN);
+    checked.extend_from_slice(bytes);
+    checked.extend_from_slice(&checksum(bytes));
+    checked
+}
+
+/// # \[Constant-Time] Verify Checksum
+///
+/// Splits the checksum off the end of `checked` and verifies it, returning the payload.
+///
+/// Returns `SlugEncodingError::ChecksumMismatch` if the checksum does not match (or `checked` is shorter than the checksum).
+pub fn verify_checksum(checked: &[u8]) -> Result<&[u8], SlugEncodingError> {
+    let payload_len = checked.len().checked_sub(CHECKSUM_LEN).ok_or(SlugEncodingError::ChecksumMismatch)?;
+    let (payload, expected) = checked.split_at(payload_len);
+
+    match bool::from(checksum(payload).ct_eq(expected)) {
+        true => Ok(payload),
+        false => Err(SlugEncodingError::ChecksumMismatch),
+    }
+}
+
+#[test]
+fn checksum_roundtrip() {
+    let checked = append_checksum(b"slugencode");
+    assert_eq!(checked.len(), 10 + CHECKSUM_LEN);
+    assert_eq!(verify_checksum(&checked).unwrap(), b"slugencode");
+
+    let mut tampered = checked.clone();
+    tampered[0] ^= 1;
+    assert_eq!(verify_checksum(&tampered), Err(SlugEncodingError::ChecksumMismatch));
+    assert_eq!(verify_checksum(&checked[..3]), Err(SlugEncodingError::ChecksumMismatch));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn checked_usage() {
+    use crate::{SlugEncodingUsage, SlugEncodings};
+
+    for encoding in [SlugEncodings::Hex, SlugEncodings::Base32, SlugEncodings::Base58, SlugEncodings::Base64urlsafe, SlugEncodings::Z85] {
+        let x = SlugEncodingUsage::new(encoding);
+        let encoded = x.encode_checked([1u8;32]).unwrap();
+        assert_eq!(x.decode_checked(&encoded).unwrap(), [1u8;32]);
+    }
+
+    let x = SlugEncodingUsage::new(SlugEncodings::Hex);
+    let mut encoded = x.encode_checked(b"key").unwrap();
+    encoded.replace_range(0..1, "f");
+    assert_eq!(x.decode_checked(&encoded), Err(SlugEncodingError::ChecksumMismatch));
+}
@@ -0,0 +1,283 @@
+//! # Streaming I/O
+//!
+//! Requires the `std` feature.
+//!
+//! `SlugEncoderWriter` and `SlugDecoderReader` encode/decode through `std::io::Write`/`std::io::Read` a block at a time, so large inputs never have to be loaded into memory. They are created with `SlugEncodingUsage::encoder_writer`/`decoder_reader` and support every encoding `SlugStreamEncoder` supports (Base58, Ascii85 and Bech32 return `SlugEncodingError::StreamingUnsupported`).
+
+use std::io::{self, Read, Write};
+
+use crate::errors::SlugEncodingError;
+use crate::stream::{SlugStreamDecoder, SlugStreamEncoder};
+use crate::{wipe, SlugEncodingUsage};
+
+/// Size of the internal buffers.
+const BUFFER_SIZE: usize = 1024;
+
+/// The underlying writer is only taken by `finish()`/`into_inner()`, which consume the writer.
+pub(crate) const TAKEN: &str = "the underlying writer is only taken when the writer is consumed";
+
+/// Converts a `SlugEncodingError` into an `io::Error` (`ErrorKind::InvalidData`).
+pub(crate) fn io_error(e: SlugEncodingError) -> io::Error {
+    return io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+/// # SlugEncoderWriter
+///
+/// Encodes everything written to it and writes the encoded string to `W`.
+///
+/// A trailing partial block is kept back until `finish()` encodes (and pads) it, so `finish()` must be called once all the data is written. The internal buffer is wiped on drop.
+///
+/// ## Example Code
+///
+/// ```rust
+/// use std::io::Write;
+/// use slugencode::prelude::*;
+///
+/// fn main() {
+///     let mut writer = SlugEncodingUsage::new(SlugEncodings::Base64).encoder_writer(Vec::new()).unwrap();
+///
+///     writer.write_all(b"slug").unwrap();
+///     writer.write_all(b"encode").unwrap();
+///
+///     let encoded = writer.finish().unwrap();
+///     assert_eq!(encoded, b"c2x1Z2VuY29kZQ==");
+/// }
+/// ```
+pub struct SlugEncoderWriter<W: Write> {
+    /// Only taken by `finish()`, so it is always `Some` otherwise
+    inner: Option<W>,
+    encoder: SlugStreamEncoder,
+    buffer: [u8;BUFFER_SIZE],
+}
+
+impl<W: Write> SlugEncoderWriter<W> {
+    pub(crate) fn new(usage: SlugEncodingUsage, inner: W) -> Result<Self, SlugEncodingError> {
+        return Ok(Self {
+            inner: Some(inner),
+            encoder: SlugStreamEncoder::new(usage)?,
+            buffer: [0u8;BUFFER_SIZE],
+        })
+    }
+    /// Gets a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        return self.inner.as_ref().expect(TAKEN)
+    }
+    /// Encodes (and pads) the last partial block, flushes and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let written = self.encoder.finish(&mut self.buffer).map_err(io_error)?;
+        let inner = self.inner.as_mut().expect(TAKEN);
+        let result = inner.write_all(&self.buffer[..written]);
+        wipe(&mut self.buffer[..written]);
+        result?;
+        inner.flush()?;
+        return Ok(self.inner.take().expect(TAKEN))
+    }
+}
+
+impl<W: Write> Write for SlugEncoderWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let (consumed, written) = self.encoder.update_partial(buf, &mut self.buffer).map_err(io_error)?;
+        let result = self.inner.as_mut().expect(TAKEN).write_all(&self.buffer[..written]);
+        wipe(&mut self.buffer[..written]);
+        result?;
+        return Ok(consumed)
+    }
+    /// Flushes the underlying writer (a partial block is only written by `finish()`).
+    fn flush(&mut self) -> io::Result<()> {
+        return self.inner.as_mut().expect(TAKEN).flush()
+    }
+}
+
+impl<W: Write> Drop for SlugEncoderWriter<W> {
+    fn drop(&mut self) {
+        wipe(&mut self.buffer);
+    }
+}
+
+/// # SlugDecoderReader
+///
+/// Reads an encoded string from `R` and returns the decoded bytes.
+///
+/// Invalid input is reported as an `io::Error` of kind `InvalidData` wrapping the `SlugEncodingError` (with the position in the whole stream). The internal buffers are wiped on drop.
+///
+/// ## Example Code
+///
+/// ```rust
+/// use std::io::Read;
+/// use slugencode::prelude::*;
+///
+/// fn main() {
+///     let encoded: &[u8] = b"c2x1Z2VuY29kZQ==";
+///     let mut reader = SlugEncodingUsage::new(SlugEncodings::Base64).decoder_reader(encoded).unwrap();
+///
+///     let mut decoded = Vec::new();
+///     reader.read_to_end(&mut decoded).unwrap();
+///     assert_eq!(decoded, b"slugencode");
+/// }
+/// ```
+pub struct SlugDecoderReader<R: Read> {
+    inner: R,
+    decoder: SlugStreamDecoder,
+    input: [u8;BUFFER_SIZE],
+    input_pos: usize,
+    input_len: usize,
+    output: [u8;BUFFER_SIZE],
+    output_pos: usize,
+    output_len: usize,
+    eof: bool,
+}
+
+impl<R: Read> SlugDecoderReader<R> {
+    pub(crate) fn new(usage: SlugEncodingUsage, inner: R) -> Result<Self, SlugEncodingError> {
+        return Ok(Self {
+            inner,
+            decoder: SlugStreamDecoder::new(usage)?,
+            input: [0u8;BUFFER_SIZE],
+            input_pos: 0,
+            input_len: 0,
+            output: [0u8;BUFFER_SIZE],
+            output_pos: 0,
+            output_len: 0,
+            eof: false,
+        })
+    }
+    /// Gets a reference to the underlying reader
+    pub fn get_ref(&self) -> &R {
+        return &self.inner
+    }
+}
+
+impl<R: Read> Read for SlugDecoderReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0)
+        }
+
+        loop {
+            if self.output_pos < self.output_len {
+                let len = buf.len().min(self.output_len - self.output_pos);
+                buf[..len].copy_from_slice(&self.output[self.output_pos..self.output_pos + len]);
+                wipe(&mut self.output[self.output_pos..self.output_pos + len]);
+                self.output_pos += len;
+                return Ok(len)
+            }
+
+            if self.eof {
+                return Ok(0)
+            }
+
+            // Decode straight into `buf` when a whole block fits
+            let direct = buf.len() >= self.decoder.min_output();
+
+            if self.input_pos == self.input_len {
+                let read = self.inner.read(&mut self.input)?;
+
+                if read == 0 {
+                    self.eof = true;
+                    let output = if direct { &mut buf[..] } else { &mut self.output[..] };
+                    let written = self.decoder.finish(output).map_err(io_error)?;
+
+                    if direct {
+                        return Ok(written)
+                    }
+                    (self.output_pos, self.output_len) = (0, written);
+                    continue;
+                }
+                (self.input_pos, self.input_len) = (0, read);
+            }
+
+            let input = &self.input[self.input_pos..self.input_len];
+            let output = if direct { &mut buf[..] } else { &mut self.output[..] };
+            let (consumed, written) = self.decoder.update_partial(input, output).map_err(io_error)?;
+            self.input_pos += consumed;
+
+            if direct && written > 0 {
+                return Ok(written)
+            }
+            (self.output_pos, self.output_len) = (0, if direct { 0 } else { written });
+        }
+    }
+}
+
+impl<R: Read> Drop for SlugDecoderReader<R> {
+    fn drop(&mut self) {
+        wipe(&mut self.input);
+        wipe(&mut self.output);
+    }
+}
+
+#[test]
+fn stream_roundtrip() {
+    use crate::base58::Base58Alphabet;
+    use crate::SlugEncodings;
+
+    let data: Vec<u8> = (0..5000u32).map(|i| (i * 7 + i / 13) as u8).collect();
+    let encodings = [
+        SlugEncodings::Hex,
+        SlugEncodings::Base32,
+        SlugEncodings::Base32unpadded,
+        SlugEncodings::Base64,
+        SlugEncodings::Base64urlsafe,
+        SlugEncodings::Base85rfc1924,
+        SlugEncodings::Base58monero,
+    ];
+
+    // The Base58 alphabet of the usage must be kept when streaming
+    let usages = encodings.into_iter().map(SlugEncodingUsage::new)
+        .chain([SlugEncodingUsage::new(SlugEncodings::Base58monero).with_alphabet(Base58Alphabet::RIPPLE)]);
+
+    for usage in usages {
+        for len in [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 1500, 5000] {
+            let expected = usage.encode(&data[..len]).unwrap();
+
+            // Writes of every size (including ones that split blocks)
+            let mut writer = usage.encoder_writer(Vec::new()).unwrap();
+            for chunk in data[..len].chunks(1 + len % 7) {
+                writer.write_all(chunk).unwrap();
+            }
+            let encoded = writer.finish().unwrap();
+            assert_eq!(encoded, expected.as_bytes());
+
+            // Reads into a small buffer (less than a block) and a large one
+            for size in [1, 4096] {
+                let mut reader = usage.decoder_reader(encoded.as_slice()).unwrap();
+                let mut decoded = Vec::new();
+                let mut buf = vec![0u8; size];
+                loop {
+                    let n = reader.read(&mut buf).unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    decoded.extend_from_slice(&buf[..n]);
+                }
+                assert_eq!(decoded, &data[..len]);
+            }
+        }
+    }
+}
+
+#[test]
+fn stream_errors() {
+    use crate::SlugEncodings;
+
+    for encoding in [SlugEncodings::Base58, SlugEncodings::Base58fast, SlugEncodings::Ascii85] {
+        let usage = crate::SlugEncodingUsage::new(encoding);
+        assert_eq!(usage.encoder_writer(Vec::new()).err(), Some(SlugEncodingError::StreamingUnsupported { encoding }));
+        assert!(usage.decoder_reader(&b""[..]).is_err());
+    }
+
+    // The position is counted from the start of the stream
+    let encoded = [b"QUJD".repeat(400), b"QU*D".to_vec()].concat();
+    let mut reader = crate::SlugEncodingUsage::new(SlugEncodings::Base64).decoder_reader(encoded.as_slice()).unwrap();
+    let e = reader.read_to_end(&mut Vec::new()).unwrap_err();
+    let e = e.get_ref().unwrap().downcast_ref::<SlugEncodingError>().unwrap();
+    assert_eq!((e.position(), e.character()), (Some(1602), Some('*')));
+
+    // Nothing may follow padding
+    let mut reader = crate::SlugEncodingUsage::new(SlugEncodings::Base64).decoder_reader(&b"QQ==QUJD"[..]).unwrap();
+    assert_eq!(reader.read_to_end(&mut Vec::new()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    // A truncated (padded) stream fails at the end
+    let mut reader = crate::SlugEncodingUsage::new(SlugEncodings::Base32).decoder_reader(&b"MZXW6YQ"[..]).unwrap();
+    assert!(reader.read_to_end(&mut Vec::new()).is_err());
+}
@@ -8,15 +8,36 @@
 //! 
 //! ## Features
 //! 
-//! - [X] Encodings (Hex, Base32, Base58, Base64, Bytes)
-//! - [] Try Get Encoding (using RegEx) (feature)
-//! - [] Large Unsigned Integer (feature)
-//! - [] Zeroize (feature)
-//! - [] integrity-check (feature) (uses BLAKE2s)
-//! - [] Add Base85 (not constant-time)
+//! - [X] Encodings (Hex, Base32, Base58, Base64, Base85, Bytes)
+//! - [X] Constant-Time Base58 (with `bs58` as the fast variant) and Base58 alphabets (Bitcoin, Ripple, Flickr or your own)
+//! - [X] Monero Base58 (block-wise, constant-time) for Monero addresses
+//! - [X] Try Get Encoding
+//! - [X] Allocation-free encoding/decoding into caller-provided buffers
+//! - [X] `no_std` (`std` is a default feature, `alloc` enables the `String`/`Vec` APIs)
+//! - [X] Large Unsigned Integer (u8-u128, U256, U512)
+//! - [X] Zeroize (feature)
+//! - [X] integrity-check (feature) (uses BLAKE2s)
+//! - [X] Streaming (`std::io`, `tokio` (feature) and push-based `SlugStreamEncoder`/`SlugStreamDecoder`)
+//! - [X] serde (feature) (`#[serde(with = "slugencode::serde::hex")]`)
+//! - [X] Add Base85 (not constant-time) (Ascii85, Z85, RFC1924)
+//! - [X] Transcoding between encodings (`SlugEncodingUsage::transcode`, `SlugTranscoder`)
+//! - [X] Validated encoded-string types (`HexString`, `Base64String`, `Encoded<E>`, ...) with constant-time equality
+//! - [X] Constant-time verification (`SlugEncodingUsage::verify`, `verify::SlugCtEq`)
+//! - [X] Command-line tool (`slugencode`)
+//! - [X] Bech32/Bech32m (BIP-173, BIP-350) with HRP handling and error location
+//! - [X] base58check (feature) (version bytes, double SHA-256 checksum and well-known version prefixes)
 //! - [] Add feature Cert
 //! 
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![allow(clippy::needless_return, clippy::let_and_return)]
+#![cfg_attr(test, allow(unused_variables, clippy::println_empty_string))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
 
 // TODO:
 // [X] From_Hex
@@ -36,7 +57,7 @@
 // [X] To_bs32
 // [X] To_bs32_unpadded
 
-// Add u64, u128, u256 ...
+// [X] Add u64, u128, u256 ... (see `integers`)
 
 // Hexadecimal
 use ct_codecs::{Decoder, Encoder, Hex};
@@ -50,19 +71,38 @@ use base32ct::Error as Bs32Error;
 use base32ct::{Base32,Base32Unpadded};
 use base32ct::Encoding;
 
-// Base58
-use bs58;
-use bs58::encode::Error as bs58Error;
-use bs58::decode::Error as bs58DecodingError;
-
-// Errors
-use ct_codecs::Error;
-
 
 pub mod errors;
+mod encodings;
 pub mod prelude;
-
-use errors::SlugEncodingError;
+pub mod base58;
+pub mod base85;
+pub mod bech32;
+#[cfg(feature = "base58check")]
+pub mod base58check;
+#[cfg(feature = "alloc")]
+pub mod detect;
+#[cfg(feature = "zeroize")]
+pub mod secret;
+#[cfg(feature = "integrity-check")]
+pub mod integrity;
+pub mod integers;
+pub mod stream;
+#[cfg(feature = "alloc")]
+pub mod transcode;
+#[cfg(feature = "alloc")]
+pub mod encoded;
+pub mod verify;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "tokio")]
+pub mod async_io;
+#[cfg(feature = "serde")]
+pub mod serde;
+
+use errors::{bs58_decoding_error, decoding_error, diagnose, encoding_error, SlugEncodingError};
+#[cfg(feature = "alloc")]
+use detect::{try_get_encoding, SlugEncodingCandidate, AMBIGUITY_MARGIN};
 
 /// # SlugEncodings
 /// 
@@ -71,9 +111,16 @@ use errors::SlugEncodingError;
 /// - Hexadecimal (CT)
 /// - Base32 RFC4648 (CT)
 /// - Base32 RFC4648 Unpadded (CT)
-/// - Base58 (Not CT)
+/// - Base58 (CT)
+/// - Base58 Fast (Not CT)
+/// - Base58 Monero (CT) (8-byte blocks)
 /// - Base64 (CT)
 /// - Base64 URL Safe (CT)
+/// - Ascii85 (Not CT)
+/// - Z85 (Not CT)
+/// - Base85 RFC1924 (Not CT)
+/// - Bech32 (Not CT) (needs an HRP to encode, see `SlugEncodingUsage::with_hrp`)
+/// - Bech32m (Not CT)
 /// 
 /// ### Example Code
 /// 
@@ -86,67 +133,227 @@ use errors::SlugEncodingError;
 ///     let encoding_base32 = SlugEncodings::Base32;
 ///     let encoding_base32_unpadded = SlugEncodings::Base32unpadded;
 ///     let encoding_base58 = SlugEncodings::Base58;
+///     let encoding_base58_fast = SlugEncodings::Base58fast;
+///     let encoding_base58_monero = SlugEncodings::Base58monero;
 ///     let encoding_base64 = SlugEncodings::Base64;
 ///     let encoding_base64_url_safe = SlugEncodings::Base64urlsafe;
+///     let encoding_ascii85 = SlugEncodings::Ascii85;
+///     let encoding_z85 = SlugEncodings::Z85;
+///     let encoding_base85_rfc1924 = SlugEncodings::Base85rfc1924;
+///     let encoding_bech32 = SlugEncodings::Bech32;
+///     let encoding_bech32m = SlugEncodings::Bech32m;
 /// }
 /// ```
-#[derive(Clone,Copy,Debug,PartialEq,PartialOrd,Hash)]
+#[derive(Clone,Copy,Debug,PartialEq,Eq,PartialOrd,Hash)]
 pub enum SlugEncodings {
     Hex,
     Base32,
     Base32unpadded,
     Base58,
+    Base58fast,
+    Base58monero,
     Base64,
     Base64urlsafe,
+    Ascii85,
+    Z85,
+    Base85rfc1924,
+    Bech32,
+    Bech32m,
 }
 
 /// # SlugEncoder
 /// 
 /// The SlugEncoder trait is used to encode bytes to different encodings like hex, base32, base58, and base64.
+/// 
+/// It is implemented for every `AsRef<[u8]>` (e.g. `Vec<u8>`, `&[u8]`, `[u8;N]`, `Box<[u8]>`, `Cow<[u8]>`, `bytes::Bytes`). Integers are encoded through `integers::SlugIntBytes` (e.g. `x.to_slug_bytes(SlugEndian::Big, SlugIntWidth::Minimal).to_hex()`).
+/// 
+/// Only `encode_to_slice` is available without the `alloc` feature.
 pub trait SlugEncoder {
+    /// # Encode To Slice
+    /// 
+    /// Encodes into `encoded` without allocating (see `SlugEncodingUsage::encode_to_slice`).
+    fn encode_to_slice<'a>(&self, encoding: SlugEncodings, encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError>;
     /// # \[Constant-Time] To Hexadecimal
     /// 
     /// Uses `ct_codecs` crate to convert bytes to hexadecimal.
     /// 
     /// Accepts as input `AsRef<[u8]>`
-    fn to_hex(&self) -> Result<String, Error>;
+    #[cfg(feature = "alloc")]
+    fn to_hex(&self) -> Result<String, SlugEncodingError>;
     /// # \[Constant-Time] To Base64 (URL SAFE) (With Padding)
     /// 
     /// Uses `ct_codecs` crate to convert bytes to base64 url safe string.
     /// 
     /// Accepts as input `AsRef<[u8]>`
-    fn to_bs64_url(&self) -> Result<String, Error>;
+    #[cfg(feature = "alloc")]
+    fn to_bs64_url(&self) -> Result<String, SlugEncodingError>;
     /// # \[Constant-Time] To Base64
     /// 
     /// Uses `ct_codecs` crate to convert bytes to base64 string.
     /// 
     /// Accepts as input `AsRef<[u8]>`
-    fn to_bs64(&self) -> Result<String, Error>;
+    #[cfg(feature = "alloc")]
+    fn to_bs64(&self) -> Result<String, SlugEncodingError>;
     /// # \[Constant-Time] To Base32
+    #[cfg(feature = "alloc")]
     fn to_bs32(&self) -> String;
     /// # \[Constant-Time] To Base32 Unpadded
+    #[cfg(feature = "alloc")]
     fn to_bs32_unpadded(&self) -> String;
-    /// Not Constant-Time
-    fn to_base58(&self) -> String;
+    /// # \[Constant-Time] To Base58
+    /// 
+    /// Uses the constant-time implementation in `slugencode::base58` with the Bitcoin alphabet.
+    #[cfg(feature = "alloc")]
+    fn to_base58(&self) -> Result<String, SlugEncodingError>;
+    /// # To Base58 (Fast) (Not Constant-Time)
+    /// 
+    /// Uses `bs58` crate to convert bytes to base58 string.
+    #[cfg(feature = "alloc")]
+    fn to_base58_fast(&self) -> String;
+    /// # \[Constant-Time] To Base58 (Monero)
+    /// 
+    /// Monero's block-wise Base58 (8-byte blocks into 11 characters) with the Bitcoin alphabet, as used by Monero addresses.
+    #[cfg(feature = "alloc")]
+    fn to_base58_monero(&self) -> Result<String, SlugEncodingError>;
+    /// # \[Constant-Time] To Base58Check
+    /// 
+    /// Prepends `version` and appends the double SHA-256 checksum (see `base58check::encode`). Requires the `base58check` feature.
+    #[cfg(all(feature = "base58check", feature = "alloc"))]
+    fn to_base58check(&self, version: &[u8]) -> Result<String, SlugEncodingError>;
+    /// # To Ascii85 (Not Constant-Time)
+    /// 
+    /// Adobe Ascii85 with the `<~` and `~>` delimiters and the `z` zero-group shortcut.
+    #[cfg(feature = "alloc")]
+    fn to_ascii85(&self) -> String;
+    /// # To Z85 (Not Constant-Time)
+    /// 
+    /// ZeroMQ Z85. The input must be a multiple of 4 bytes.
+    #[cfg(feature = "alloc")]
+    fn to_z85(&self) -> Result<String, SlugEncodingError>;
+    /// # To Base85 RFC1924 (Not Constant-Time)
+    #[cfg(feature = "alloc")]
+    fn to_bs85_rfc1924(&self) -> String;
+    /// # To Bech32 (Not Constant-Time)
+    /// 
+    /// Encodes with the human-readable prefix `hrp` (see `bech32::encode`).
+    #[cfg(feature = "alloc")]
+    fn to_bech32(&self, hrp: &str) -> Result<String, SlugEncodingError>;
+    /// # To Bech32m (Not Constant-Time)
+    #[cfg(feature = "alloc")]
+    fn to_bech32m(&self, hrp: &str) -> Result<String, SlugEncodingError>;
 }
 
-/// # SlugEncoder
+/// # SlugDecoder
+/// 
+/// The SlugDecoder trait is used to decode strings using different encodings like hex, base32, base58, and base64.
 /// 
-/// The SlugDecoder trait is used to decode bytes using different encodings like hex, base32, base58, and base64. It is implemented on bytes like Vec<u8>, Arrays, and Byte Slices
+/// It is implemented for every `AsRef<[u8]>`, which includes `String`, `&str` and the bytes of an encoded string (e.g. `Vec<u8>`). Input that is not valid UTF-8 is rejected as an invalid character.
+/// 
+/// Only `decode_to_slice` is available without the `alloc` feature.
+#[allow(clippy::wrong_self_convention)]
 pub trait SlugDecoder {
+    /// # Decode To Slice
+    /// 
+    /// Decodes into `bin` without allocating (see `SlugEncodingUsage::decode_to_slice`).
+    fn decode_to_slice<'a>(&self, encoding: SlugEncodings, bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError>;
     /// # \[Constant-Time] From Hexadecimal
     /// Uses `ct_codecs` crate to convert a hexadecimal string into a vector of bytes.
-    fn from_hex(&self) -> Result<Vec<u8>,Error>;
+    #[cfg(feature = "alloc")]
+    fn from_hex(&self) -> Result<Vec<u8>,SlugEncodingError>;
     /// # \[Constant-Time] From Base64
-    fn from_bs64(&self) -> Result<Vec<u8>,Error>;
+    #[cfg(feature = "alloc")]
+    fn from_bs64(&self) -> Result<Vec<u8>,SlugEncodingError>;
     /// # \[Constant-Time] From Base64 (URL SAFE) (With Padding)
-    fn from_bs64_url(&self) -> Result<Vec<u8>,Error>;
+    #[cfg(feature = "alloc")]
+    fn from_bs64_url(&self) -> Result<Vec<u8>,SlugEncodingError>;
     // # \[Constant-Time] From Base32
-    fn from_bs32(&self) -> Result<Vec<u8>,Bs32Error>;
+    #[cfg(feature = "alloc")]
+    fn from_bs32(&self) -> Result<Vec<u8>,SlugEncodingError>;
     // # \[Constant-Time] From Base32 Unpadded
-    fn from_bs32_unpadded(&self) -> Result<Vec<u8>,Bs32Error>;
-    /// From Base58 (Not Constant-Time)
-    fn from_base58(&self) -> Result<Vec<u8>,bs58DecodingError>;
+    #[cfg(feature = "alloc")]
+    fn from_bs32_unpadded(&self) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # \[Constant-Time] From Base58
+    /// 
+    /// Uses the constant-time implementation in `slugencode::base58` with the Bitcoin alphabet.
+    #[cfg(feature = "alloc")]
+    fn from_base58(&self) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # From Base58 (Fast) (Not Constant-Time)
+    /// 
+    /// Uses `bs58` crate to convert a base58 string into a vector of bytes.
+    #[cfg(feature = "alloc")]
+    fn from_base58_fast(&self) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # \[Constant-Time] From Base58 (Monero)
+    /// 
+    /// Decodes Monero's block-wise Base58 a chunk of 11 characters at a time.
+    /// 
+    /// Returns `SlugEncodingError::InvalidLength` if the final chunk has a length no block encodes into, and `SlugEncodingError::Overflow` if a chunk does not fit its block.
+    #[cfg(feature = "alloc")]
+    fn from_base58_monero(&self) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # \[Constant-Time] From Base58Check
+    /// 
+    /// Verifies the checksum and returns the version bytes followed by the payload (see `base58check::decode`). Requires the `base58check` feature.
+    /// 
+    /// Returns `SlugEncodingError::InvalidChecksum` if the checksum does not match.
+    #[cfg(all(feature = "base58check", feature = "alloc"))]
+    fn from_base58check(&self) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # \[Constant-Time] From Base58Check (Version)
+    /// 
+    /// Verifies the checksum and the version bytes, returning the payload (see `base58check::decode_version`).
+    /// 
+    /// Returns `SlugEncodingError::InvalidVersion` if it does not start with `version`.
+    #[cfg(all(feature = "base58check", feature = "alloc"))]
+    fn from_base58check_version(&self, version: &[u8]) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # From Ascii85 (Not Constant-Time)
+    /// 
+    /// Accepts input with or without the `<~` and `~>` delimiters.
+    #[cfg(feature = "alloc")]
+    fn from_ascii85(&self) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # From Z85 (Not Constant-Time)
+    #[cfg(feature = "alloc")]
+    fn from_z85(&self) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # From Base85 RFC1924 (Not Constant-Time)
+    #[cfg(feature = "alloc")]
+    fn from_bs85_rfc1924(&self) -> Result<Vec<u8>,SlugEncodingError>;
+    /// # From Bech32 (Not Constant-Time)
+    /// 
+    /// Verifies the checksum and returns the human-readable prefix (lowercase) and the data (see `bech32::decode`).
+    #[cfg(feature = "alloc")]
+    fn from_bech32(&self) -> Result<(String, Vec<u8>),SlugEncodingError>;
+    /// # From Bech32m (Not Constant-Time)
+    #[cfg(feature = "alloc")]
+    fn from_bech32m(&self) -> Result<(String, Vec<u8>),SlugEncodingError>;
+    /// # Decode Integer
+    /// 
+    /// Decodes an integer (`u8` to `u128`, `U256` or `U512`) encoded with `usage` (a `SlugEncodingUsage` or `SlugEncodings`) in the given byte order (see `integers::SlugInteger::from_encoded`).
+    /// 
+    /// Returns `SlugEncodingError::IntegerOverflow` if the value does not fit in the integer.
+    #[cfg(feature = "alloc")]
+    fn decode_integer<I: integers::SlugInteger>(&self, usage: impl Into<SlugEncodingUsage>, endian: integers::SlugEndian) -> Result<I,SlugEncodingError>;
+
+    /// # \[Constant-Time] From Hexadecimal (Array)
+    /// 
+    /// Decodes straight into `[u8;N]` (without allocating, see `SlugEncodingUsage::decode_into`). Returns `SlugEncodingError::LengthMismatch` if the input does not decode to exactly `N` bytes (or `SlugEncodingError::InputTooLong` if it is too long to decode without a larger buffer).
+    fn from_hex_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # \[Constant-Time] From Base64 (Array)
+    fn from_bs64_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # \[Constant-Time] From Base64 (URL SAFE) (Array)
+    fn from_bs64_url_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # \[Constant-Time] From Base32 (Array)
+    fn from_bs32_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # \[Constant-Time] From Base32 Unpadded (Array)
+    fn from_bs32_unpadded_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # \[Constant-Time] From Base58 (Array)
+    fn from_base58_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # From Base58 (Fast) (Array) (Not Constant-Time)
+    fn from_base58_fast_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # \[Constant-Time] From Base58 (Monero) (Array)
+    fn from_base58_monero_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # From Ascii85 (Array) (Not Constant-Time)
+    fn from_ascii85_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # From Z85 (Array) (Not Constant-Time)
+    fn from_z85_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
+    /// # From Base85 RFC1924 (Array) (Not Constant-Time)
+    fn from_bs85_rfc1924_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError>;
 }
 
 /*
@@ -244,165 +451,75 @@ pub trait SlugEncode {
 
 //=====IMPL SLUGENCODER=====//
 
-impl SlugEncoder for Vec<u8> {
-    fn to_hex(&self) -> Result<String, Error> {
-        let hex_str = Hex::encode_to_string(&self)?;
-        Ok(hex_str)
-    }
-    fn to_bs64(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs64_url(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64UrlSafe::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs32(&self) -> String {
-        let bs32 = Base32::encode_string(&self);
-        return bs32
-    }
-    fn to_bs32_unpadded(&self) -> String {
-        let bs32 = Base32Unpadded::encode_string(&self);
-        bs32
+impl<T: AsRef<[u8]> + ?Sized> SlugEncoder for T {
+    fn encode_to_slice<'a>(&self, encoding: SlugEncodings, encoded: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+        SlugEncodingUsage::new(encoding).encode_to_slice(self.as_ref(), encoded)
     }
-    fn to_base58(&self) -> String {
-        let s = bs58::encode(&self).into_string();
-        return s
-    }
-}
-
-impl SlugEncoder for &[u8] {
-    fn to_hex(&self) -> Result<String, Error> {
-        let hex_str = Hex::encode_to_string(&self)?;
+    #[cfg(feature = "alloc")]
+    fn to_hex(&self) -> Result<String, SlugEncodingError> {
+        let hex_str = encode_string(SlugEncodingUsage::new(SlugEncodings::Hex), self.as_ref())?;
         Ok(hex_str)
     }
-    fn to_bs64(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64::encode_to_string(&self)?;
+    #[cfg(feature = "alloc")]
+    fn to_bs64(&self) -> Result<String, SlugEncodingError> {
+        let bs64_url_str = encode_string(SlugEncodingUsage::new(SlugEncodings::Base64), self.as_ref())?;
         Ok(bs64_url_str)
     }
-    fn to_bs64_url(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64UrlSafe::encode_to_string(&self)?;
+    #[cfg(feature = "alloc")]
+    fn to_bs64_url(&self) -> Result<String, SlugEncodingError> {
+        let bs64_url_str = encode_string(SlugEncodingUsage::new(SlugEncodings::Base64urlsafe), self.as_ref())?;
         Ok(bs64_url_str)
     }
+    #[cfg(feature = "alloc")]
     fn to_bs32(&self) -> String {
-        let bs32 = Base32::encode_string(&self);
+        let bs32 = Base32::encode_string(self.as_ref());
         return bs32
     }
+    #[cfg(feature = "alloc")]
     fn to_bs32_unpadded(&self) -> String {
-        let bs32 = Base32Unpadded::encode_string(&self);
+        let bs32 = Base32Unpadded::encode_string(self.as_ref());
         bs32
     }
-    fn to_base58(&self) -> String {
-        let s = bs58::encode(&self).into_string();
-        return s
-    }
-}
-
-impl SlugEncoder for [u8;28] {
-    fn to_hex(&self) -> Result<String, Error> {
-        let hex_str = Hex::encode_to_string(&self)?;
-        Ok(hex_str)
-    }
-    fn to_bs64(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs64_url(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64UrlSafe::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs32(&self) -> String {
-        let bs32 = Base32::encode_string(self);
-        return bs32
-    }
-    fn to_bs32_unpadded(&self) -> String {
-        let bs32 = Base32Unpadded::encode_string(self);
-        bs32
+    #[cfg(feature = "alloc")]
+    fn to_base58(&self) -> Result<String, SlugEncodingError> {
+        let s = base58::encode_to_string(self, base58::BITCOIN_ALPHABET)?;
+        Ok(s)
     }
-    fn to_base58(&self) -> String {
-        let s = bs58::encode(&self).into_string();
+    #[cfg(feature = "alloc")]
+    fn to_base58_fast(&self) -> String {
+        let s = bs58::encode(self).into_string();
         return s
     }
-}
-
-impl SlugEncoder for [u8;32] {
-    fn to_hex(&self) -> Result<String, Error> {
-        let hex_str = Hex::encode_to_string(&self)?;
-        Ok(hex_str)
-    }
-    fn to_bs64(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs64_url(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64UrlSafe::encode_to_string(&self)?;
-        Ok(bs64_url_str)
+    #[cfg(feature = "alloc")]
+    fn to_base58_monero(&self) -> Result<String, SlugEncodingError> {
+        return base58::encode_monero_to_string(self, base58::BITCOIN_ALPHABET)
     }
-    fn to_bs32(&self) -> String {
-        let bs32 = Base32::encode_string(self);
-        return bs32
-    }
-    fn to_bs32_unpadded(&self) -> String {
-        let bs32 = Base32Unpadded::encode_string(self);
-        bs32
+    #[cfg(all(feature = "base58check", feature = "alloc"))]
+    fn to_base58check(&self, version: &[u8]) -> Result<String, SlugEncodingError> {
+        return base58check::encode(version, self)
     }
-    fn to_base58(&self) -> String {
-        let s = bs58::encode(&self).into_string();
+    #[cfg(feature = "alloc")]
+    fn to_ascii85(&self) -> String {
+        let s = base85::encode_ascii85(self);
         return s
     }
-}
-
-impl SlugEncoder for [u8;48] {
-    fn to_hex(&self) -> Result<String, Error> {
-        let hex_str = Hex::encode_to_string(&self)?;
-        Ok(hex_str)
+    #[cfg(feature = "alloc")]
+    fn to_z85(&self) -> Result<String, SlugEncodingError> {
+        let s = base85::encode_z85(self)?;
+        Ok(s)
     }
-    fn to_bs64(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs64_url(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64UrlSafe::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs32(&self) -> String {
-        let bs32 = Base32::encode_string(self);
-        return bs32
-    }
-    fn to_bs32_unpadded(&self) -> String {
-        let bs32 = Base32Unpadded::encode_string(self);
-        bs32
-    }
-    fn to_base58(&self) -> String {
-        let s = bs58::encode(&self).into_string();
+    #[cfg(feature = "alloc")]
+    fn to_bs85_rfc1924(&self) -> String {
+        let s = base85::encode_rfc1924(self);
         return s
     }
-}
-
-impl SlugEncoder for [u8;64] {
-    fn to_hex(&self) -> Result<String, Error> {
-        let hex_str = Hex::encode_to_string(&self)?;
-        Ok(hex_str)
-    }
-    fn to_bs64(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs64_url(&self) -> Result<String, Error> {
-        let bs64_url_str = Base64UrlSafe::encode_to_string(&self)?;
-        Ok(bs64_url_str)
-    }
-    fn to_bs32(&self) -> String {
-        let bs32 = Base32::encode_string(self);
-        return bs32
-    }
-    fn to_bs32_unpadded(&self) -> String {
-        let bs32 = Base32Unpadded::encode_string(self);
-        bs32
+    #[cfg(feature = "alloc")]
+    fn to_bech32(&self, hrp: &str) -> Result<String, SlugEncodingError> {
+        return bech32::encode(hrp, self, bech32::Bech32Variant::Bech32)
     }
-    fn to_base58(&self) -> String {
-        let s = bs58::encode(&self).into_string();
-        return s
+    #[cfg(feature = "alloc")]
+    fn to_bech32m(&self, hrp: &str) -> Result<String, SlugEncodingError> {
+        return bech32::encode(hrp, self, bech32::Bech32Variant::Bech32m)
     }
 }
 
@@ -411,14 +528,18 @@ impl SlugEncoder for [u8;64] {
 /// 
 /// A struct for easy use in conversion. Contains the slugencodings enum that holds the different encodings
 /// 
+/// Bech32 and Bech32m also need a human-readable prefix to encode (`with_hrp`). When decoding, only the data is returned (and the prefix is checked if one was set).
+/// 
+/// Base58, Base58 Fast and Base58 Monero use the Bitcoin alphabet unless another one is set with `with_alphabet`.
+/// 
 /// ## Example Code
 /// 
 /// ```rust
-/// use slugencodings::SlugAPI;
-/// use slugencodings::SlugEncodings;
+/// use slugencode::SlugEncodingUsage;
+/// use slugencode::SlugEncodings;
 /// 
 /// fn main() {
-///     let encoder = SlugAPI::new(SlugEncodings::Hex);
+///     let encoder = SlugEncodingUsage::new(SlugEncodings::Hex);
 /// }
 /// ```
 /// 
@@ -426,177 +547,654 @@ impl SlugEncoder for [u8;64] {
 #[derive(Clone,Copy,Debug,PartialEq,PartialOrd,Hash)]
 pub struct SlugEncodingUsage {
     encoding: SlugEncodings,
+    hrp: bech32::Hrp,
+    alphabet: base58::Base58Alphabet,
 }
 
 impl SlugEncodingUsage {
     /// Creates a new instance using the intended encoding/decoding
     pub fn new(encoding: SlugEncodings) -> Self {
         return Self {
-            encoding: encoding,
+            encoding,
+            hrp: bech32::Hrp::NONE,
+            alphabet: base58::Base58Alphabet::BITCOIN,
         }
     }
+    /// # With HRP
+    /// 
+    /// Sets the human-readable prefix Bech32 and Bech32m encode with (and expect when decoding). The other encodings ignore it.
+    /// 
+    /// Returns `SlugEncodingError::InvalidHrp` if it is empty, longer than `bech32::MAX_HRP_LENGTH`, not printable ASCII or mixed-case.
+    /// 
+    /// ```rust
+    /// use slugencode::prelude::*;
+    /// 
+    /// # #[cfg(feature = "alloc")]
+    /// fn main() {
+    ///     let x = SlugEncodingUsage::new(SlugEncodings::Bech32).with_hrp("npub").unwrap();
+    ///     let encoded = x.encode([0u8;32]).unwrap();
+    /// 
+    ///     assert!(encoded.starts_with("npub1"));
+    ///     assert_eq!(x.decode(&encoded).unwrap(), [0u8;32]);
+    /// }
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() {}
+    /// ```
+    pub fn with_hrp<T: AsRef<str>>(self, hrp: T) -> Result<Self,SlugEncodingError> {
+        let hrp = bech32::Hrp::new(hrp.as_ref(), self.encoding)?;
+        return Ok(Self { hrp, ..self })
+    }
+    /// # With Alphabet
+    /// 
+    /// Sets the alphabet Base58, Base58 Fast and Base58 Monero encode and decode with (Bitcoin by default). The other encodings ignore it.
+    /// 
+    /// ```rust
+    /// use slugencode::prelude::*;
+    /// 
+    /// # #[cfg(feature = "alloc")]
+    /// fn main() {
+    ///     let x = SlugEncodingUsage::new(SlugEncodings::Base58).with_alphabet(Base58Alphabet::RIPPLE);
+    /// 
+    ///     assert_eq!(x.encode([0u8;4]).unwrap(), "rrrr");
+    ///     assert_eq!(x.decode("rrrr").unwrap(), [0u8;4]);
+    /// }
+    /// # #[cfg(not(feature = "alloc"))]
+    /// # fn main() {}
+    /// ```
+    pub fn with_alphabet(self, alphabet: base58::Base58Alphabet) -> Self {
+        return Self { alphabet, ..self }
+    }
     /// Gets encoding
     pub fn get_encoding(&self) -> SlugEncodings {
         return self.encoding
     }
-    pub fn decode<T: AsRef<str>>(&self, encoded_str: T) -> Result<Vec<u8>,SlugEncodingError> {
-        match self.encoding {
-            SlugEncodings::Hex => {
-                let decoding = encoded_str.as_ref().from_hex();
-
-                match decoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::DecodingError)
-                }
-            }
-            SlugEncodings::Base32 => {
-                let decoding = encoded_str.as_ref().from_bs32();
+    /// Gets the human-readable prefix (if one was set)
+    pub fn get_hrp(&self) -> Option<&str> {
+        return self.hrp.as_str()
+    }
+    /// Gets the Base58 alphabet
+    pub fn get_alphabet(&self) -> base58::Base58Alphabet {
+        return self.alphabet
+    }
+    /// # Decode Any
+    /// 
+    /// Detects the encoding of `encoded_str` using `detect::try_get_encoding` and decodes it.
+    /// 
+    /// Returns `SlugEncodingError::AmbiguousEncoding` if another candidate is within `detect::AMBIGUITY_MARGIN` of the best one but decodes to different bytes, and `SlugEncodingError::UnknownEncoding` if nothing decodes.
+    #[cfg(feature = "alloc")]
+    pub fn decode_any<T: AsRef<str>>(encoded_str: T) -> Result<SlugEncodingCandidate,SlugEncodingError> {
+        let mut candidates = try_get_encoding(encoded_str).into_iter();
 
-                match decoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::DecodingError)
-                }
-            }
-            SlugEncodings::Base32unpadded => {
-                let decoding = encoded_str.as_ref().from_bs32_unpadded();
+        let best = candidates.next().ok_or(SlugEncodingError::UnknownEncoding)?;
 
-                match decoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::DecodingError)
-                }
+        for other in candidates {
+            if best.confidence - other.confidence < AMBIGUITY_MARGIN && other.decoded != best.decoded {
+                return Err(SlugEncodingError::AmbiguousEncoding)
             }
-            SlugEncodings::Base58 => {
-                let decoding = encoded_str.as_ref().from_base58();
-
-                match decoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::DecodingError)
-                }
+        }
+        return Ok(best)
+    }
+    /// # Transcode
+    /// 
+    /// Decodes `encoded_str` with `from` and encodes the bytes with `to` (each a `SlugEncodingUsage`, so the Bech32 prefix and Base58 alphabet are kept, or a `SlugEncodings`). The decoded bytes are wiped afterwards (and kept in a `Zeroizing` buffer with the `zeroize` feature). See `transcode::SlugTranscoder` to convert input in fragments.
+    /// 
+    /// ```rust
+    /// use slugencode::prelude::*;
+    /// 
+    /// fn main() {
+    ///     let npub = SlugEncodingUsage::new(SlugEncodings::Bech32).with_hrp("npub").unwrap();
+    ///     let encoded = SlugEncodingUsage::transcode("00".repeat(32), SlugEncodings::Hex, npub).unwrap();
+    /// 
+    ///     assert!(encoded.starts_with("npub1"));
+    ///     assert_eq!(SlugEncodingUsage::transcode(&encoded, npub, SlugEncodings::Hex).unwrap(), "00".repeat(32));
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn transcode<T: AsRef<[u8]>, F: Into<SlugEncodingUsage>, U: Into<SlugEncodingUsage>>(encoded_str: T, from: F, to: U) -> Result<String,SlugEncodingError> {
+        #[cfg(feature = "zeroize")]
+        let decoded = from.into().decode_zeroizing(encoded_str)?;
+        #[cfg(not(feature = "zeroize"))]
+        let mut decoded = from.into().decode(encoded_str)?;
+
+        let encoded = to.into().encode(&decoded[..]);
+        #[cfg(not(feature = "zeroize"))]
+        wipe(&mut decoded);
+        return encoded
+    }
+    /// # Encode (Zeroizing)
+    /// 
+    /// Same as `SecretSlugEncodingUsage::encode`. Requires the `zeroize` feature.
+    #[cfg(feature = "zeroize")]
+    pub fn encode_zeroizing<T: AsRef<[u8]>>(&self, bytes: T) -> Result<zeroize::Zeroizing<String>,SlugEncodingError> {
+        secret::SecretSlugEncodingUsage::from(*self).encode(bytes)
+    }
+    /// # Decode (Zeroizing)
+    /// 
+    /// Same as `SecretSlugEncodingUsage::decode`. Requires the `zeroize` feature.
+    #[cfg(feature = "zeroize")]
+    pub fn decode_zeroizing<T: AsRef<[u8]>>(&self, encoded_str: T) -> Result<zeroize::Zeroizing<Vec<u8>>,SlugEncodingError> {
+        secret::SecretSlugEncodingUsage::from(*self).decode(encoded_str)
+    }
+    /// # Encode (Checked)
+    /// 
+    /// Appends a truncated BLAKE2s checksum (see `integrity`) to `bytes` and encodes the result. Requires the `integrity-check` feature.
+    #[cfg(all(feature = "integrity-check", feature = "alloc"))]
+    pub fn encode_checked<T: AsRef<[u8]>>(&self, bytes: T) -> Result<String,SlugEncodingError> {
+        let mut checked = integrity::append_checksum(bytes);
+        let encoding = self.encode(&checked);
+        wipe(&mut checked);
+        encoding
+    }
+    /// # Decode (Checked)
+    /// 
+    /// Decodes `encoded_str` and verifies its checksum in constant-time, returning the payload without the checksum. Requires the `integrity-check` feature.
+    /// 
+    /// Returns `SlugEncodingError::ChecksumMismatch` if the checksum does not match.
+    #[cfg(all(feature = "integrity-check", feature = "alloc"))]
+    pub fn decode_checked<T: AsRef<[u8]>>(&self, encoded_str: T) -> Result<Vec<u8>,SlugEncodingError> {
+        let mut checked = self.decode(encoded_str)?;
+        let payload_len = integrity::verify_checksum(&checked).map(|payload| payload.len());
+
+        match payload_len {
+            Ok(len) => {
+                wipe(&mut checked[len..]);
+                checked.truncate(len);
+                Ok(checked)
             }
-            SlugEncodings::Base64 => {
-                let decoding = encoded_str.as_ref().from_bs64();
-
-                match decoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::DecodingError)
-                }
+            Err(e) => {
+                wipe(&mut checked);
+                Err(e)
             }
-            SlugEncodings::Base64urlsafe => {
-                let decoding = encoded_str.as_ref().from_bs64_url();
+        }
+    }
+    /// # Verify
+    /// 
+    /// Decodes `encoded_str` and compares it with `expected` in constant-time (see `verify`), returning a `subtle::Choice` that is true only if it is valid and decodes to exactly `expected`.
+    /// 
+    /// ```rust
+    /// use slugencode::prelude::*;
+    /// 
+    /// fn main() {
+    ///     let x = SlugEncodingUsage::new(SlugEncodings::Base64urlsafe);
+    /// 
+    ///     assert!(bool::from(x.verify([0xfb, 0xff], "-_8=")));
+    ///     assert!(!bool::from(x.verify([0xfb, 0xfe], "-_8=")));
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn verify<E: AsRef<[u8]>, T: AsRef<[u8]>>(&self, expected: E, encoded_str: T) -> subtle::Choice {
+        let encoded_str = encoded_str.as_ref();
+        let mut decoded = vec![0u8; self.decoded_len(encoded_str.len())];
+
+        let choice = match self.decode_to_slice(encoded_str, &mut decoded) {
+            Ok(bin) => verify::ct_eq(bin, expected),
+            Err(_) => subtle::Choice::from(0),
+        };
+        wipe(&mut decoded);
+        return choice
+    }
+    /// # Decode
+    /// 
+    /// Decodes `encoded_str` with the chosen encoding.
+    #[cfg(feature = "alloc")]
+    pub fn decode<T: AsRef<[u8]>>(&self, encoded_str: T) -> Result<Vec<u8>,SlugEncodingError> {
+        let encoded_str = encoded_str.as_ref();
 
-                match decoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::DecodingError)
-                }
-            }
+        match self.encoding {
+            SlugEncodings::Hex => encoded_str.from_hex(),
+            SlugEncodings::Base32 => encoded_str.from_bs32(),
+            SlugEncodings::Base32unpadded => encoded_str.from_bs32_unpadded(),
+            SlugEncodings::Base58 | SlugEncodings::Base58fast | SlugEncodings::Base58monero => decode_vec(*self, encoded_str),
+            SlugEncodings::Base64 => encoded_str.from_bs64(),
+            SlugEncodings::Base64urlsafe => encoded_str.from_bs64_url(),
+            SlugEncodings::Ascii85 => encoded_str.from_ascii85(),
+            SlugEncodings::Z85 => encoded_str.from_z85(),
+            SlugEncodings::Base85rfc1924 => encoded_str.from_bs85_rfc1924(),
+            SlugEncodings::Bech32 | SlugEncodings::Bech32m => decode_vec(*self, encoded_str),
         }
     }
+    /// # Decode Into Array
+    /// 
+    /// Decodes `encoded_str` straight into `[u8;N]` (e.g. a 32-byte key) with the chosen encoding, without allocating. The working buffer is on the stack (`2 * N` bytes, at least 64) and is wiped afterwards.
+    /// 
+    /// Returns `SlugEncodingError::LengthMismatch` if it does not decode to exactly `N` bytes, or `SlugEncodingError::InputTooLong` if it is too long to decode without a larger buffer.
+    /// 
+    /// ```rust
+    /// use slugencode::prelude::*;
+    /// 
+    /// fn main() {
+    ///     let x = SlugEncodingUsage::new(SlugEncodings::Hex);
+    ///     let key: [u8;4] = x.decode_into("deadbeef").unwrap();
+    /// 
+    ///     assert_eq!(key, [0xde, 0xad, 0xbe, 0xef]);
+    ///     assert!(x.decode_into::<_, 32>("deadbeef").is_err());
+    /// }
+    /// ```
+    pub fn decode_into<T: AsRef<[u8]>, const N: usize>(&self, encoded_str: T) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(*self, encoded_str.as_ref())
+    }
+    /// # Encode
+    /// 
+    /// Encodes `bytes` with the chosen encoding.
+    #[cfg(feature = "alloc")]
     pub fn encode<T: AsRef<[u8]>>(&self, bytes: T) -> Result<String,SlugEncodingError> {
+        let bytes = bytes.as_ref();
+
         match self.encoding {
-            SlugEncodings::Hex => {
-                let encoding = bytes.as_ref().to_hex();
-                match encoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::Failed)
-                }
-            }
-            SlugEncodings::Base32 => {
-                Ok(bytes.as_ref().to_bs32())
-            }
-            SlugEncodings::Base32unpadded => {
-                Ok(bytes.as_ref().to_bs32_unpadded())
-            }
-            SlugEncodings::Base58 => {
-                Ok(bytes.as_ref().to_base58())
-            }
-            SlugEncodings::Base64 => {
-                let encoding =  bytes.as_ref().to_bs64();
+            SlugEncodings::Hex => bytes.to_hex(),
+            SlugEncodings::Base32 => Ok(bytes.to_bs32()),
+            SlugEncodings::Base32unpadded => Ok(bytes.to_bs32_unpadded()),
+            SlugEncodings::Base58 | SlugEncodings::Base58fast | SlugEncodings::Base58monero => encode_string(*self, bytes),
+            SlugEncodings::Base64 => bytes.to_bs64(),
+            SlugEncodings::Base64urlsafe => bytes.to_bs64_url(),
+            SlugEncodings::Ascii85 => Ok(bytes.to_ascii85()),
+            SlugEncodings::Z85 => bytes.to_z85(),
+            SlugEncodings::Base85rfc1924 => Ok(bytes.to_bs85_rfc1924()),
+            SlugEncodings::Bech32 | SlugEncodings::Bech32m => encode_string(*self, bytes),
+        }
+    }
+    /// # Stream Encoder
+    /// 
+    /// Creates a `SlugStreamEncoder` that encodes bytes pushed in fragments of any size.
+    /// 
+    /// Returns `SlugEncodingError::StreamingUnsupported` for Base58, Ascii85 and Bech32.
+    pub fn stream_encoder(&self) -> Result<stream::SlugStreamEncoder,SlugEncodingError> {
+        return stream::SlugStreamEncoder::new(*self)
+    }
+    /// # Stream Decoder
+    /// 
+    /// Creates a `SlugStreamDecoder` that decodes characters pushed in fragments of any size.
+    /// 
+    /// Returns `SlugEncodingError::StreamingUnsupported` for Base58, Ascii85 and Bech32.
+    pub fn stream_decoder(&self) -> Result<stream::SlugStreamDecoder,SlugEncodingError> {
+        return stream::SlugStreamDecoder::new(*self)
+    }
+    /// # Encoder Writer
+    /// 
+    /// Wraps `writer` in a `SlugEncoderWriter` that encodes everything written to it a block at a time (call `finish()` to write the final, padded block).
+    /// 
+    /// Returns `SlugEncodingError::StreamingUnsupported` for Base58, Ascii85 and Bech32.
+    #[cfg(feature = "std")]
+    pub fn encoder_writer<W: std::io::Write>(&self, writer: W) -> Result<io::SlugEncoderWriter<W>,SlugEncodingError> {
+        return io::SlugEncoderWriter::new(*self, writer)
+    }
+    /// # Decoder Reader
+    /// 
+    /// Wraps `reader` in a `SlugDecoderReader` that decodes the encoded string read from it a block at a time.
+    /// 
+    /// Returns `SlugEncodingError::StreamingUnsupported` for Base58, Ascii85 and Bech32.
+    #[cfg(feature = "std")]
+    pub fn decoder_reader<R: std::io::Read>(&self, reader: R) -> Result<io::SlugDecoderReader<R>,SlugEncodingError> {
+        return io::SlugDecoderReader::new(*self, reader)
+    }
+    /// # Async Encoder Writer
+    /// 
+    /// Wraps `writer` in an `AsyncSlugEncoderWriter` (`tokio::io::AsyncWrite`) that encodes everything written to it a block at a time (shut it down to write the final, padded block).
+    /// 
+    /// Returns `SlugEncodingError::StreamingUnsupported` for Base58, Ascii85 and Bech32.
+    #[cfg(feature = "tokio")]
+    pub fn async_encoder_writer<W: tokio::io::AsyncWrite + Unpin>(&self, writer: W) -> Result<async_io::AsyncSlugEncoderWriter<W>,SlugEncodingError> {
+        return async_io::AsyncSlugEncoderWriter::new(*self, writer)
+    }
+    /// # Async Decoder Reader
+    /// 
+    /// Wraps `reader` in an `AsyncSlugDecoderReader` (`tokio::io::AsyncRead`) that decodes the encoded string read from it a block at a time.
+    /// 
+    /// Returns `SlugEncodingError::StreamingUnsupported` for Base58, Ascii85 and Bech32.
+    #[cfg(feature = "tokio")]
+    pub fn async_decoder_reader<R: tokio::io::AsyncRead + Unpin>(&self, reader: R) -> Result<async_io::AsyncSlugDecoderReader<R>,SlugEncodingError> {
+        return async_io::AsyncSlugDecoderReader::new(*self, reader)
+    }
+    /// # Encoded Length
+    /// 
+    /// The size of the buffer `encode_to_slice` needs for `bin_len` bytes (the maximum length of the encoded string).
+    pub fn encoded_len(&self, bin_len: usize) -> usize {
+        match self.encoding {
+            SlugEncodings::Hex => bin_len * 2,
+            SlugEncodings::Base32 => bin_len.div_ceil(5) * 8,
+            SlugEncodings::Base32unpadded => (bin_len * 8).div_ceil(5),
+            SlugEncodings::Base58 | SlugEncodings::Base58fast => base58::encoded_len(bin_len),
+            SlugEncodings::Base58monero => base58::monero_encoded_len(bin_len),
+            SlugEncodings::Base64 | SlugEncodings::Base64urlsafe => bin_len.div_ceil(3) * 4,
+            SlugEncodings::Ascii85 => base85::ascii85_encoded_len(bin_len),
+            SlugEncodings::Z85 | SlugEncodings::Base85rfc1924 => base85::encoded_len(bin_len),
+            SlugEncodings::Bech32 | SlugEncodings::Bech32m => bech32::encoded_len(self.hrp.as_str().map_or(1, str::len), bin_len),
+        }
+    }
+    /// # Decoded Length
+    /// 
+    /// The size of the buffer `decode_to_slice` needs for `encoded_len` characters (the maximum length of the decoded bytes).
+    pub fn decoded_len(&self, encoded_len: usize) -> usize {
+        match self.encoding {
+            SlugEncodings::Hex => encoded_len / 2,
+            SlugEncodings::Base32 | SlugEncodings::Base32unpadded => encoded_len * 5 / 8,
+            SlugEncodings::Base58 | SlugEncodings::Base58fast => base58::decoded_len(encoded_len),
+            SlugEncodings::Base58monero => base58::monero_decoded_len(encoded_len),
+            SlugEncodings::Base64 | SlugEncodings::Base64urlsafe => encoded_len * 3 / 4,
+            SlugEncodings::Ascii85 => base85::ascii85_decoded_len(encoded_len),
+            SlugEncodings::Z85 | SlugEncodings::Base85rfc1924 => base85::decoded_len(encoded_len),
+            SlugEncodings::Bech32 | SlugEncodings::Bech32m => bech32::decoded_len(encoded_len),
+        }
+    }
+    /// # Encode To Slice
+    /// 
+    /// Encodes `bin` into `encoded` without allocating, returning the string as a sub-slice of `encoded`.
+    /// 
+    /// Returns `SlugEncodingError::BufferTooSmall` if `encoded` is shorter than `encoded_len(bin.len())` (Ascii85 only needs room for its actual output), and `InvalidHrp` for Bech32 without a human-readable prefix.
+    /// 
+    /// ```rust
+    /// use slugencode::prelude::*;
+    /// 
+    /// fn main() {
+    ///     let x = SlugEncodingUsage::new(SlugEncodings::Base64);
+    ///     let mut buf = [0u8;64];
+    /// 
+    ///     let encoded = x.encode_to_slice(b"slugencode", &mut buf).unwrap();
+    ///     assert_eq!(encoded, "c2x1Z2VuY29kZQ==");
+    /// }
+    /// ```
+    pub fn encode_to_slice<'a>(&self, bin: &[u8], encoded: &'a mut [u8]) -> Result<&'a str,SlugEncodingError> {
+        let required = self.encoded_len(bin.len());
 
-                match encoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::Failed)
-                }
+        if self.bech32_variant().is_some() && self.hrp.as_str().is_none() {
+            return Err(SlugEncodingError::InvalidHrp { encoding: self.encoding })
+        }
+        if encoded.len() < required && self.encoding != SlugEncodings::Ascii85 {
+            return Err(SlugEncodingError::BufferTooSmall { encoding: self.encoding, required })
+        }
+
+        match self.encoding {
+            SlugEncodings::Hex => Hex::encode_to_str(encoded, bin).map_err(encoding_error(self.encoding)),
+            SlugEncodings::Base64 => Base64::encode_to_str(encoded, bin).map_err(encoding_error(self.encoding)),
+            SlugEncodings::Base64urlsafe => Base64UrlSafe::encode_to_str(encoded, bin).map_err(encoding_error(self.encoding)),
+            SlugEncodings::Base32 => Base32::encode(bin, encoded).map_err(encoding_error(self.encoding)),
+            SlugEncodings::Base32unpadded => Base32Unpadded::encode(bin, encoded).map_err(encoding_error(self.encoding)),
+            SlugEncodings::Base58 => base58::encode_to_slice(bin, self.alphabet.as_bytes(), encoded),
+            SlugEncodings::Base58fast => {
+                let alphabet = self.bs58_alphabet()?;
+                let len = bs58::encode(bin).with_alphabet(&alphabet).onto(&mut encoded[..]).map_err(encoding_error(self.encoding))?;
+                core::str::from_utf8(&encoded[..len]).map_err(|_| SlugEncodingError::EncodingError { encoding: self.encoding, source: None })
             }
-            SlugEncodings::Base64urlsafe => {
-                let encoding =  bytes.as_ref().to_bs64_url();
+            SlugEncodings::Base58monero => base58::encode_monero_to_slice(bin, self.alphabet.as_bytes(), encoded),
+            SlugEncodings::Ascii85 => base85::encode_ascii85_to_slice(bin, encoded),
+            SlugEncodings::Z85 => base85::encode_z85_to_slice(bin, encoded),
+            SlugEncodings::Base85rfc1924 => base85::encode_rfc1924_to_slice(bin, encoded),
+            SlugEncodings::Bech32 => bech32::encode_to_slice(self.hrp.as_str().unwrap_or_default(), bin, bech32::Bech32Variant::Bech32, encoded),
+            SlugEncodings::Bech32m => bech32::encode_to_slice(self.hrp.as_str().unwrap_or_default(), bin, bech32::Bech32Variant::Bech32m, encoded),
+        }
+    }
+    /// # Decode To Slice
+    /// 
+    /// Decodes `encoded_str` into `bin` without allocating, returning the bytes as a sub-slice of `bin`.
+    /// 
+    /// Returns `SlugEncodingError::BufferTooSmall` if `bin` is shorter than `decoded_len(encoded_str.len())` (Ascii85 only needs room for its actual output), and `InvalidHrp` if a Bech32 string does not have the prefix set with `with_hrp`.
+    pub fn decode_to_slice<'a, T: AsRef<[u8]>>(&self, encoded_str: T, bin: &'a mut [u8]) -> Result<&'a [u8],SlugEncodingError> {
+        let encoded = encoded_str.as_ref();
+        let required = self.decoded_len(encoded.len());
+
+        if bin.len() < required && self.encoding != SlugEncodings::Ascii85 {
+            return Err(SlugEncodingError::BufferTooSmall { encoding: self.encoding, required })
+        }
 
-                match encoding {
-                    Ok(v) => return Ok(v),
-                    Err(_) => return Err(SlugEncodingError::Failed)
+        match self.encoding {
+            SlugEncodings::Hex => Hex::decode(bin, encoded, None).map_err(decoding_error(self.encoding, encoded)),
+            SlugEncodings::Base64 => Base64::decode(bin, encoded, None).map_err(decoding_error(self.encoding, encoded)),
+            SlugEncodings::Base64urlsafe => Base64UrlSafe::decode(bin, encoded, None).map_err(decoding_error(self.encoding, encoded)),
+            SlugEncodings::Base32 => Base32::decode(bs32_check(encoded, self.encoding)?, bin).map_err(decoding_error(self.encoding, encoded)),
+            SlugEncodings::Base32unpadded => Base32Unpadded::decode(bs32_check(encoded, self.encoding)?, bin).map_err(decoding_error(self.encoding, encoded)),
+            SlugEncodings::Base58 => base58::decode_to_slice(encoded, self.alphabet.as_bytes(), bin),
+            SlugEncodings::Base58fast => {
+                let alphabet = self.bs58_alphabet()?;
+                let len = bs58::decode(encoded).with_alphabet(&alphabet).onto(&mut bin[..]).map_err(bs58_decoding_error(encoded))?;
+                Ok(&bin[..len])
+            }
+            SlugEncodings::Base58monero => base58::decode_monero_to_slice(encoded, self.alphabet.as_bytes(), bin),
+            SlugEncodings::Ascii85 => base85::decode_ascii85_to_slice(encoded, bin),
+            SlugEncodings::Z85 => base85::decode_z85_to_slice(encoded, bin),
+            SlugEncodings::Base85rfc1924 => base85::decode_rfc1924_to_slice(encoded, bin),
+            SlugEncodings::Bech32 | SlugEncodings::Bech32m => {
+                let variant = self.bech32_variant().unwrap_or(bech32::Bech32Variant::Bech32);
+                let (hrp, data) = bech32::decode_to_slice(encoded, variant, bin)?;
+                let (matches, len) = (self.hrp.as_str().is_none_or(|expected| expected.eq_ignore_ascii_case(hrp)), data.len());
+
+                if !matches {
+                    wipe(&mut bin[..len]);
+                    return Err(SlugEncodingError::InvalidHrp { encoding: self.encoding })
                 }
+                Ok(&bin[..len])
             }
         }
     }
+    /// The Bech32 variant (if the encoding is Bech32 or Bech32m).
+    fn bech32_variant(&self) -> Option<bech32::Bech32Variant> {
+        match self.encoding {
+            SlugEncodings::Bech32 => Some(bech32::Bech32Variant::Bech32),
+            SlugEncodings::Bech32m => Some(bech32::Bech32Variant::Bech32m),
+            _ => None,
+        }
+    }
+    /// The Base58 alphabet for `bs58` (already validated by `Base58Alphabet`).
+    fn bs58_alphabet(&self) -> Result<bs58::Alphabet,SlugEncodingError> {
+        return bs58::Alphabet::new(self.alphabet.as_bytes()).map_err(|_| SlugEncodingError::InvalidAlphabet { encoding: self.encoding, index: None })
+    }
 }
 
-impl SlugDecoder for String {
-    fn from_hex(&self) -> Result<Vec<u8>,Error> {
-        let output = Hex::decode_to_vec(&self, None)?;
-        Ok(output)
+impl From<SlugEncodings> for SlugEncodingUsage {
+    fn from(encoding: SlugEncodings) -> Self {
+        return Self::new(encoding)
     }
-    fn from_bs64(&self) -> Result<Vec<u8>,Error> {
-        let output = Base64::decode_to_vec(&self, None)?;
-        Ok(output)
+}
+
+/// Wipes a working buffer (using `zeroize` when the feature is enabled).
+///
+/// Without `zeroize` the zeroed buffer is passed through `core::hint::black_box`, so the compiler has to assume it is read afterwards and cannot drop the writes as dead stores.
+#[inline]
+pub(crate) fn wipe(buf: &mut [u8]) {
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(buf);
+    #[cfg(not(feature = "zeroize"))]
+    {
+        buf.fill(0);
+        core::hint::black_box(buf);
+    }
+}
+
+/// `base32ct` only accepts `&str` and panics on inputs whose final group has 1, 3 or 6 characters (which no encoder produces), so those are rejected before decoding.
+pub(crate) fn bs32_check(input: &[u8], encoding: SlugEncodings) -> Result<&str,SlugEncodingError> {
+    let invalid = || diagnose(encoding, input, Some(Bs32Error::InvalidEncoding.into()));
+
+    let input_str = core::str::from_utf8(input).map_err(|_| invalid())?;
+    let unpadded = match encoding {
+        SlugEncodings::Base32 => input_str.trim_end_matches('='),
+        _ => input_str,
+    };
+
+    match unpadded.len() % 8 {
+        1 | 3 | 6 => Err(invalid()),
+        _ => Ok(input_str),
     }
-    fn from_bs64_url(&self) -> Result<Vec<u8>,Error> {
-        let output = Base64UrlSafe::decode_to_vec(&self, None)?;
+}
+
+impl<T: AsRef<[u8]> + ?Sized> SlugDecoder for T {
+    fn decode_to_slice<'a>(&self, encoding: SlugEncodings, bin: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+        SlugEncodingUsage::new(encoding).decode_to_slice(self, bin)
+    }
+    #[cfg(feature = "alloc")]
+    fn from_hex(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let output = decode_vec(SlugEncodingUsage::new(SlugEncodings::Hex), self.as_ref())?;
         Ok(output)
     }
-    fn from_bs32(&self) -> Result<Vec<u8>,Bs32Error> {
-        let output = Base32::decode_vec(&self)?;
+    #[cfg(feature = "alloc")]
+    fn from_bs64(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let output = decode_vec(SlugEncodingUsage::new(SlugEncodings::Base64), self.as_ref())?;
         Ok(output)
     }
-    fn from_bs32_unpadded(&self) -> Result<Vec<u8>,Bs32Error> {
-        let output = Base32Unpadded::decode_vec(&self)?;
+    #[cfg(feature = "alloc")]
+    fn from_bs64_url(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let output = decode_vec(SlugEncodingUsage::new(SlugEncodings::Base64urlsafe), self.as_ref())?;
         Ok(output)
     }
-    fn from_base58(&self) -> Result<Vec<u8>,bs58DecodingError> {
-        let output = bs58::decode(&self).into_vec()?;
+    #[cfg(feature = "alloc")]
+    fn from_bs32(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let input = bs32_check(self.as_ref(), SlugEncodings::Base32)?;
+        let output = Base32::decode_vec(input).map_err(decoding_error(SlugEncodings::Base32, self.as_ref()))?;
         Ok(output)
     }
-}
-
-impl SlugDecoder for &str {
-fn from_hex(&self) -> Result<Vec<u8>,Error> {
-        let output = Hex::decode_to_vec(&self, None)?;
+    #[cfg(feature = "alloc")]
+    fn from_bs32_unpadded(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let input = bs32_check(self.as_ref(), SlugEncodings::Base32unpadded)?;
+        let output = Base32Unpadded::decode_vec(input).map_err(decoding_error(SlugEncodings::Base32unpadded, self.as_ref()))?;
         Ok(output)
     }
-    fn from_bs64(&self) -> Result<Vec<u8>,Error> {
-        let output = Base64::decode_to_vec(&self, None)?;
+    #[cfg(feature = "alloc")]
+    fn from_base58(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let output = base58::decode_to_vec(self, base58::BITCOIN_ALPHABET)?;
         Ok(output)
     }
-    fn from_bs64_url(&self) -> Result<Vec<u8>,Error> {
-        let output = Base64UrlSafe::decode_to_vec(&self, None)?;
+    #[cfg(feature = "alloc")]
+    fn from_base58_fast(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let output = bs58::decode(self).into_vec().map_err(bs58_decoding_error(self.as_ref()))?;
         Ok(output)
     }
-    fn from_bs32(&self) -> Result<Vec<u8>,Bs32Error> {
-        let output = Base32::decode_vec(&self)?;
+    #[cfg(feature = "alloc")]
+    fn from_base58_monero(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        return base58::decode_monero_to_vec(self, base58::BITCOIN_ALPHABET)
+    }
+    #[cfg(all(feature = "base58check", feature = "alloc"))]
+    fn from_base58check(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        return base58check::decode(self)
+    }
+    #[cfg(all(feature = "base58check", feature = "alloc"))]
+    fn from_base58check_version(&self, version: &[u8]) -> Result<Vec<u8>,SlugEncodingError> {
+        return base58check::decode_version(self, version)
+    }
+    #[cfg(feature = "alloc")]
+    fn from_ascii85(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let output = base85::decode_ascii85(self)?;
         Ok(output)
     }
-    fn from_bs32_unpadded(&self) -> Result<Vec<u8>,Bs32Error> {
-        let output = Base32Unpadded::decode_vec(&self)?;
+    #[cfg(feature = "alloc")]
+    fn from_z85(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let output = base85::decode_z85(self)?;
         Ok(output)
     }
-    fn from_base58(&self) -> Result<Vec<u8>,bs58DecodingError> {
-        let output = bs58::decode(&self).into_vec()?;
+    #[cfg(feature = "alloc")]
+    fn from_bs85_rfc1924(&self) -> Result<Vec<u8>,SlugEncodingError> {
+        let output = base85::decode_rfc1924(self)?;
         Ok(output)
     }
+    #[cfg(feature = "alloc")]
+    fn from_bech32(&self) -> Result<(String, Vec<u8>),SlugEncodingError> {
+        return bech32::decode(self, bech32::Bech32Variant::Bech32)
+    }
+    #[cfg(feature = "alloc")]
+    fn from_bech32m(&self) -> Result<(String, Vec<u8>),SlugEncodingError> {
+        return bech32::decode(self, bech32::Bech32Variant::Bech32m)
+    }
+    #[cfg(feature = "alloc")]
+    fn decode_integer<I: integers::SlugInteger>(&self, usage: impl Into<SlugEncodingUsage>, endian: integers::SlugEndian) -> Result<I,SlugEncodingError> {
+        return I::from_encoded(self, usage, endian)
+    }
+
+    fn from_hex_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Hex), self.as_ref())
+    }
+    fn from_bs64_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Base64), self.as_ref())
+    }
+    fn from_bs64_url_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Base64urlsafe), self.as_ref())
+    }
+    fn from_bs32_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Base32), self.as_ref())
+    }
+    fn from_bs32_unpadded_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Base32unpadded), self.as_ref())
+    }
+    fn from_base58_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Base58), self.as_ref())
+    }
+    fn from_base58_fast_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Base58fast), self.as_ref())
+    }
+    fn from_base58_monero_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Base58monero), self.as_ref())
+    }
+    fn from_ascii85_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Ascii85), self.as_ref())
+    }
+    fn from_z85_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Z85), self.as_ref())
+    }
+    fn from_bs85_rfc1924_array<const N: usize>(&self) -> Result<[u8;N],SlugEncodingError> {
+        return decode_array(SlugEncodingUsage::new(SlugEncodings::Base85rfc1924), self.as_ref())
+    }
+}
+
+/// Encodes into a `String` through `encode_to_slice` (`ct_codecs` only allocates with `std`).
+#[cfg(feature = "alloc")]
+fn encode_string(usage: SlugEncodingUsage, bin: &[u8]) -> Result<String,SlugEncodingError> {
+    let encoding = usage.encoding;
+
+    let mut encoded = vec![0u8; usage.encoded_len(bin.len())];
+    let len = usage.encode_to_slice(bin, &mut encoded)?.len();
+    encoded.truncate(len);
+
+    String::from_utf8(encoded).map_err(|_| SlugEncodingError::EncodingError { encoding, source: None })
+}
+
+/// Decodes into a `Vec` through `decode_to_slice` (`ct_codecs` only allocates with `std`).
+#[cfg(feature = "alloc")]
+fn decode_vec(usage: SlugEncodingUsage, encoded: &[u8]) -> Result<Vec<u8>,SlugEncodingError> {
+    let mut bin = vec![0u8; usage.decoded_len(encoded.len())];
+    let len = usage.decode_to_slice(encoded, &mut bin)?.len();
+    bin.truncate(len);
+    Ok(bin)
+}
+
+/// The smallest working buffer `decode_array` uses (enough for the padding of a short input and any Bech32 string).
+const MIN_ARRAY_WORK: usize = 64;
+
+/// Decodes into `[u8;N]` through `decode_to_slice`, using a working buffer on the stack (`2 * N` bytes, at least `MIN_ARRAY_WORK`) that fits any input of `N` bytes, and wipes it afterwards.
+/// 
+/// Input that is too long for the working buffer (so its decoded length is unknown) is reported as `InputTooLong`.
+fn decode_array<const N: usize>(usage: SlugEncodingUsage, encoded: &[u8]) -> Result<[u8;N],SlugEncodingError> {
+    let encoding = usage.encoding;
+
+    let mut large = [[0u8;N];2];
+    let mut small = [0u8;MIN_ARRAY_WORK];
+    let work = if N * 2 >= MIN_ARRAY_WORK { large.as_flattened_mut() } else { &mut small[..] };
+
+    let output = match usage.decode_to_slice(encoded, &mut work[..]) {
+        Ok(decoded) if decoded.len() == N => {
+            let mut output = [0u8;N];
+            output.copy_from_slice(decoded);
+            Ok(output)
+        }
+        Ok(decoded) => Err(SlugEncodingError::LengthMismatch { encoding, expected: N, actual: decoded.len() }),
+        Err(SlugEncodingError::BufferTooSmall { .. }) => Err(SlugEncodingError::InputTooLong { encoding, expected: N, length: encoded.len() }),
+        Err(e) => Err(e),
+    };
+    wipe(work);
+    return output
 }
 
 
 
 
 
+#[cfg(feature = "alloc")]
 #[test]
 fn run() {
     use self::SlugEncoder;
     let bytes: [u8;30] = [33u8;30];
     let byte_slice = bytes.as_slice();
-    let output = byte_slice.to_base58();
+    let output = byte_slice.to_base58().unwrap();
 
     println!("Output: {}", output);
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn slugencoder() {
     use self::SlugEncoder;
@@ -614,12 +1212,13 @@ fn slugencoder() {
     println!("HEX: {}",bytes.to_hex().unwrap());
     println!("Base32: {}", bytes.to_bs32());
     println!("Base32_unpadded: {}", bytes.to_bs32_unpadded());
-    println!("Base58: {}", bytes.to_base58());
+    println!("Base58: {}", bytes.to_base58().unwrap());
     println!("Base64: {}",bytes.to_bs64().unwrap());
     println!("Base64_URL_SAFE: {}",bytes.to_bs64_url().unwrap());
 
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn slugapi() {
     let x = SlugEncodingUsage::new(SlugEncodings::Base64);
@@ -628,6 +1227,7 @@ fn slugapi() {
     println!("Output: {}", output)
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn slugdecoder() {
     use self::SlugEncoder;
@@ -645,7 +1245,7 @@ fn slugdecoder() {
     println!("HEX: {}",bytes.to_hex().unwrap());
     println!("Base32: {}", bytes.to_bs32());
     println!("Base32_unpadded: {}", bytes.to_bs32_unpadded());
-    println!("Base58: {}", bytes.to_base58());
+    println!("Base58: {}", bytes.to_base58().unwrap());
     println!("Base64: {}",bytes.to_bs64().unwrap());
     println!("Base64_URL_SAFE: {}",bytes.to_bs64_url().unwrap());
 
@@ -655,6 +1255,7 @@ fn slugdecoder() {
     println!("Decoded Output: {:?}", output)
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn slugapi_usage() {
     let message_512: &str = "62928ef1f4effcfcba350e9e033ed8c07a94066654e1a4be79dd72027f3828480a7c517266a04fd747a8702d7087a298484c525bdd1b54997bb5eca1a6219b58";
@@ -663,4 +1264,94 @@ fn slugapi_usage() {
     let output = x.decode(message_512).unwrap();
 
     println!("Output: {:?}", output)
-}
\ No newline at end of file
+}
+#[cfg(feature = "alloc")]
+#[test]
+fn slugencoder_base58_fast() {
+    let message_512: &str = "62928ef1f4effcfcba350e9e033ed8c07a94066654e1a4be79dd72027f3828480a7c517266a04fd747a8702d7087a298484c525bdd1b54997bb5eca1a6219b58";
+
+    assert_eq!(b"Hello World!".to_base58_fast(), "2NEpo7TZRRrLZSi2U");
+    assert_eq!([0u8, 0, 1].to_base58_fast(), "112");
+    assert_eq!("2NEpo7TZRRrLZSi2U".from_base58_fast().unwrap(), b"Hello World!");
+
+    // Both implementations produce the same strings
+    assert_eq!("4144675a6e958d60".as_bytes().to_base58_fast(), "4144675a6e958d60".as_bytes().to_base58().unwrap());
+    assert_eq!(message_512.as_bytes().to_base58_fast(), message_512.as_bytes().to_base58().unwrap());
+    assert_eq!(message_512.as_bytes().to_base58_fast().from_base58().unwrap(), message_512.as_bytes());
+}
+#[cfg(feature = "alloc")]
+#[test]
+fn slugencoder_containers() {
+    use std::borrow::Cow;
+
+    let expected = "000102030405060708090a0b0c0d0e0f";
+    let bytes: [u8;16] = core::array::from_fn(|i| i as u8);
+
+    assert_eq!(bytes.to_hex().unwrap(), expected);
+    assert_eq!([7u8;33].to_base58().unwrap(), [7u8;33].to_base58_fast());
+    assert_eq!(Box::<[u8]>::from(bytes).to_hex().unwrap(), expected);
+    assert_eq!(Cow::Borrowed(&bytes[..]).to_hex().unwrap(), expected);
+
+    assert_eq!(expected.from_hex().unwrap(), bytes);
+    assert_eq!(expected.as_bytes().to_vec().from_hex().unwrap(), bytes);
+    assert_eq!(SlugEncodingUsage::new(SlugEncodings::Hex).decode(expected.as_bytes()).unwrap(), bytes);
+
+    let e = [0x61u8, 0xff].from_bs32().unwrap_err();
+    assert_eq!(e.position(), Some(1));
+}
+#[cfg(feature = "alloc")]
+#[test]
+fn slugdecoder_arrays() {
+    let key = [42u8;32];
+
+    for encoding in [SlugEncodings::Hex, SlugEncodings::Base32, SlugEncodings::Base32unpadded, SlugEncodings::Base58, SlugEncodings::Base58fast, SlugEncodings::Base64, SlugEncodings::Base64urlsafe, SlugEncodings::Ascii85, SlugEncodings::Z85, SlugEncodings::Base85rfc1924] {
+        let x = SlugEncodingUsage::new(encoding);
+        let encoded = x.encode(key).unwrap();
+
+        assert_eq!(x.decode_into::<_, 32>(&encoded).unwrap(), key);
+        assert_eq!(x.decode_into::<_, 31>(&encoded), Err(SlugEncodingError::LengthMismatch { encoding, expected: 31, actual: 32 }));
+        assert_eq!(x.decode_into::<_, 33>(&encoded), Err(SlugEncodingError::LengthMismatch { encoding, expected: 33, actual: 32 }));
+    }
+
+    let e = "00zz".from_hex_array::<2>().unwrap_err();
+    assert_eq!(e.position(), Some(2));
+
+    // Short arrays (padding, Ascii85 `z` groups) and input too long for the working buffer
+    assert_eq!("_w==".from_bs64_url_array::<1>(), Ok([0xff]));
+    assert_eq!("ae======".from_bs32_array::<1>(), Ok([0x01]));
+    assert_eq!("<~zz~>".from_ascii85_array::<8>(), Ok([0u8;8]));
+    assert_eq!("00".repeat(80).from_hex_array::<4>(), Err(SlugEncodingError::InputTooLong { encoding: SlugEncodings::Hex, expected: 4, length: 160 }));
+    assert_eq!("00".repeat(80).from_hex_array::<4>().unwrap_err().to_string(), "Hex input of 160 bytes is too long to decode to 4 bytes");
+    assert_eq!([1u8;20].to_base58_monero().unwrap().from_base58_monero_array::<20>(), Ok([1u8;20]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn slugapi_slices() {
+    let bytes: Vec<u8> = (0..37u8).map(|i| i.wrapping_mul(97)).collect();
+
+    for encoding in [SlugEncodings::Hex, SlugEncodings::Base32, SlugEncodings::Base32unpadded, SlugEncodings::Base58, SlugEncodings::Base58fast, SlugEncodings::Base64, SlugEncodings::Base64urlsafe, SlugEncodings::Ascii85, SlugEncodings::Base85rfc1924] {
+        let x = SlugEncodingUsage::new(encoding);
+        let mut encoded = vec![0u8; x.encoded_len(bytes.len())];
+        let encoded = x.encode_to_slice(&bytes, &mut encoded).unwrap();
+        assert_eq!(encoded, x.encode(&bytes).unwrap());
+
+        let mut decoded = vec![0u8; x.decoded_len(encoded.len())];
+        assert_eq!(x.decode_to_slice(encoded, &mut decoded).unwrap(), bytes);
+
+        assert!(matches!(x.encode_to_slice(&bytes, &mut [0u8;8]), Err(SlugEncodingError::BufferTooSmall { .. })));
+        assert!(matches!(x.decode_to_slice(encoded, &mut [0u8;8]), Err(SlugEncodingError::BufferTooSmall { .. })));
+    }
+
+    let mut encoded = [0u8;8];
+    let mut decoded = [0u8;4];
+    let hex = [0xde, 0xad, 0xbe, 0xef].encode_to_slice(SlugEncodings::Hex, &mut encoded).unwrap();
+    assert_eq!(hex, "deadbeef");
+    assert_eq!("deadbeef".decode_to_slice(SlugEncodings::Hex, &mut decoded).unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+}
+
+/// Every encoding (Bech32 and Bech32m with the prefix `test`), for tests that cover all of them.
+#[cfg(all(test, feature = "alloc"))]
+pub(crate) fn test_usages() -> impl Iterator<Item = SlugEncodingUsage> {
+    return SlugEncodings::all().iter().map(|&encoding| SlugEncodingUsage::new(encoding).with_hrp("test").unwrap())
+}
@@ -1,3 +1,20 @@
 pub use crate::SlugEncodingUsage;
 pub use crate::SlugEncodings;
-pub use crate::errors::SlugEncodingError;
\ No newline at end of file
+pub use crate::errors::{SlugEncodingError, SlugBackendError};
+#[cfg(feature = "alloc")]
+pub use crate::detect::{try_get_encoding, SlugEncodingCandidate};
+#[cfg(feature = "zeroize")]
+pub use crate::secret::SecretSlugEncodingUsage;
+pub use crate::stream::{SlugStreamEncoder, SlugStreamDecoder};
+pub use crate::bech32::Bech32Variant;
+pub use crate::base58::Base58Alphabet;
+#[cfg(feature = "alloc")]
+pub use crate::transcode::SlugTranscoder;
+#[cfg(feature = "alloc")]
+pub use crate::encoded::{Encoded, SlugEncodingKind, HexString, Base32String, Base58String, Base64String, Base64UrlString};
+#[cfg(feature = "std")]
+pub use crate::io::{SlugEncoderWriter, SlugDecoderReader};
+#[cfg(feature = "tokio")]
+pub use crate::async_io::{AsyncSlugEncoderWriter, AsyncSlugDecoderReader};
+pub use crate::verify::SlugCtEq;
+pub use crate::integers::{SlugInteger, SlugEndian, SlugIntWidth, U256, U512};
\ No newline at end of file
@@ -0,0 +1,123 @@
+//! # Secret SlugAPI (Zeroize)
+//!
+//! Requires the `zeroize` feature.
+//!
+//! `SecretSlugEncodingUsage` encodes and decodes into buffers that are wrapped in `Zeroizing` from the start, so neither the plaintext nor the encoded key material is left behind when they are dropped. Everything goes through `SlugEncodingUsage::encode_to_slice`/`decode_to_slice` with a `Zeroizing` buffer (never the `String`/`Vec` helpers) so no unwiped copies are made along the way, including on errors.
+
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::errors::SlugEncodingError;
+use crate::{SlugEncodingUsage, SlugEncodings};
+
+use alloc::{string::String, vec, vec::Vec};
+
+/// # SecretSlugEncodingUsage
+///
+/// Works like `SlugEncodingUsage` but only ever returns `Zeroizing<String>` and `Zeroizing<Vec<u8>>`.
+///
+/// ## Example Code
+///
+/// ```rust
+/// use slugencode::prelude::*;
+///
+/// fn main() {
+///     let secret = SecretSlugEncodingUsage::new(SlugEncodings::Base58);
+///     let encoded = secret.encode([7u8;32]).unwrap();
+///     let decoded = secret.decode(encoded.as_str()).unwrap();
+///
+///     assert_eq!(decoded.as_slice(), &[7u8;32]);
+/// }
+/// ```
+#[derive(Clone,Copy,Debug,PartialEq,PartialOrd,Hash)]
+pub struct SecretSlugEncodingUsage {
+    usage: SlugEncodingUsage,
+}
+
+impl From<SlugEncodingUsage> for SecretSlugEncodingUsage {
+    fn from(usage: SlugEncodingUsage) -> Self {
+        Self { usage }
+    }
+}
+
+impl SecretSlugEncodingUsage {
+    /// Creates a new instance using the intended encoding/decoding
+    pub fn new(encoding: SlugEncodings) -> Self {
+        return Self {
+            usage: SlugEncodingUsage::new(encoding),
+        }
+    }
+    /// Sets the human-readable prefix for Bech32 (see `SlugEncodingUsage::with_hrp`)
+    pub fn with_hrp<T: AsRef<str>>(self, hrp: T) -> Result<Self,SlugEncodingError> {
+        return Ok(Self { usage: self.usage.with_hrp(hrp)? })
+    }
+    /// Sets the Base58 alphabet (see `SlugEncodingUsage::with_alphabet`)
+    pub fn with_alphabet(self, alphabet: crate::base58::Base58Alphabet) -> Self {
+        return Self { usage: self.usage.with_alphabet(alphabet) }
+    }
+    /// Gets encoding
+    pub fn get_encoding(&self) -> SlugEncodings {
+        return self.usage.get_encoding()
+    }
+    /// Encodes `bytes` into a string that is wiped on drop.
+    pub fn encode<T: AsRef<[u8]>>(&self, bytes: T) -> Result<Zeroizing<String>,SlugEncodingError> {
+        let bytes = bytes.as_ref();
+        let usage = self.usage;
+
+        let mut encoded = Zeroizing::new(vec![0u8; usage.encoded_len(bytes.len())]);
+        let len = usage.encode_to_slice(bytes, &mut encoded)?.len();
+        into_string(encoded, len, usage.get_encoding())
+    }
+    /// Decodes `encoded_str` into bytes that are wiped on drop.
+    pub fn decode<T: AsRef<[u8]>>(&self, encoded_str: T) -> Result<Zeroizing<Vec<u8>>,SlugEncodingError> {
+        let encoded = encoded_str.as_ref();
+        let usage = self.usage;
+
+        let mut decoded = Zeroizing::new(vec![0u8; usage.decoded_len(encoded.len())]);
+        let len = usage.decode_to_slice(encoded, &mut decoded)?.len();
+        decoded.truncate(len);
+        Ok(decoded)
+    }
+}
+
+/// Moves the first `len` bytes of an (ASCII) buffer into a `Zeroizing<String>` without copying.
+fn into_string(mut encoded: Zeroizing<Vec<u8>>, len: usize, encoding: SlugEncodings) -> Result<Zeroizing<String>,SlugEncodingError> {
+    encoded.truncate(len);
+
+    match String::from_utf8(core::mem::take(&mut *encoded)) {
+        Ok(s) => Ok(Zeroizing::new(s)),
+        Err(e) => {
+            e.into_bytes().zeroize();
+            Err(SlugEncodingError::EncodingError { encoding, source: None })
+        }
+    }
+}
+
+#[test]
+fn secret_roundtrip() {
+    let key = [0u8, 0, 1, 2, 3, 250, 251, 252, 253, 254, 255, 9];
+    let encodings = [
+        SlugEncodings::Hex,
+        SlugEncodings::Base32,
+        SlugEncodings::Base32unpadded,
+        SlugEncodings::Base58,
+        SlugEncodings::Base58fast,
+        SlugEncodings::Base58monero,
+        SlugEncodings::Base64,
+        SlugEncodings::Base64urlsafe,
+        SlugEncodings::Ascii85,
+        SlugEncodings::Z85,
+        SlugEncodings::Base85rfc1924,
+        SlugEncodings::Bech32,
+        SlugEncodings::Bech32m,
+    ];
+
+    for encoding in encodings {
+        let secret = SecretSlugEncodingUsage::new(encoding).with_hrp("key").unwrap();
+        let public = SlugEncodingUsage::new(encoding).with_hrp("key").unwrap();
+
+        let encoded = secret.encode(key).unwrap();
+        assert_eq!(encoded.as_str(), public.encode(key).unwrap());
+        assert_eq!(secret.decode(encoded.as_str()).unwrap().as_slice(), key);
+        assert!(secret.decode("~~~~~~~~").is_err());
+    }
+}
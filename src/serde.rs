@@ -0,0 +1,214 @@
+//! # Serde
+//!
+//! Requires the `serde` feature.
+//!
+//! Every `SlugEncodings` variant has a module for `#[serde(with = "...")]` on byte fields. Human-readable formats (JSON, TOML, YAML) get the encoded string, while binary formats (bincode, CBOR, postcard) get the raw bytes.
+//!
+//! - `slugencode::serde::hex`, `base32`, `base32_unpadded`, `base58`, `base58_fast`, `base58_monero`, `base64`, `base64_url`, `ascii85`, `z85` and `base85_rfc1924` work with `Vec<u8>` (anything `AsRef<[u8]>` when serializing).
+//! - Their `array` submodules (e.g. `slugencode::serde::hex::array`) work with `[u8;N]` and fail if the input is not exactly `N` bytes.
+//!
+//! Bech32 and Bech32m have no module, since the human-readable prefix cannot be passed through `#[serde(with = "...")]`.
+//!
+//! ## Example Code
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Signed {
+//!     #[serde(with = "slugencode::serde::hex::array")]
+//!     key: [u8;4],
+//!     #[serde(with = "slugencode::serde::base64_url")]
+//!     signature: Vec<u8>,
+//! }
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{SeqAccess, Visitor};
+use serde::ser::Error as _;
+use serde::{Deserializer, Serializer};
+
+use crate::errors::SlugEncodingError;
+use crate::{SlugEncodingUsage, SlugEncodings};
+
+use alloc::vec::Vec;
+
+/// Serializes `bytes` as an encoded string (human-readable formats) or as bytes (binary formats).
+fn serialize<S: Serializer>(encoding: SlugEncodings, bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        let encoded = SlugEncodingUsage::new(encoding).encode(bytes).map_err(S::Error::custom)?;
+        return serializer.serialize_str(&encoded)
+    }
+    return serializer.serialize_bytes(bytes)
+}
+
+/// Deserializes an encoded string (human-readable formats) or bytes (binary formats).
+fn deserialize<'de, D: Deserializer<'de>, T: SlugBytes>(encoding: SlugEncodings, deserializer: D) -> Result<T, D::Error> {
+    if deserializer.is_human_readable() {
+        return deserializer.deserialize_str(EncodedVisitor { encoding, output: PhantomData })
+    }
+    return deserializer.deserialize_bytes(BytesVisitor { output: PhantomData })
+}
+
+/// The types the modules deserialize into.
+trait SlugBytes: Sized {
+    fn decode(usage: SlugEncodingUsage, encoded: &str) -> Result<Self, SlugEncodingError>;
+    fn from_vec(bytes: Vec<u8>) -> Result<Self, Vec<u8>>;
+}
+
+impl SlugBytes for Vec<u8> {
+    fn decode(usage: SlugEncodingUsage, encoded: &str) -> Result<Self, SlugEncodingError> {
+        return usage.decode(encoded)
+    }
+    fn from_vec(bytes: Vec<u8>) -> Result<Self, Vec<u8>> {
+        return Ok(bytes)
+    }
+}
+
+impl<const N: usize> SlugBytes for [u8;N] {
+    fn decode(usage: SlugEncodingUsage, encoded: &str) -> Result<Self, SlugEncodingError> {
+        return usage.decode_into(encoded)
+    }
+    fn from_vec(bytes: Vec<u8>) -> Result<Self, Vec<u8>> {
+        return bytes.try_into()
+    }
+}
+
+struct EncodedVisitor<T> {
+    encoding: SlugEncodings,
+    output: PhantomData<T>,
+}
+
+impl<T: SlugBytes> Visitor<'_> for EncodedVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "a {:?} string", self.encoding)
+    }
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<T, E> {
+        return T::decode(SlugEncodingUsage::new(self.encoding), v).map_err(E::custom)
+    }
+}
+
+struct BytesVisitor<T> {
+    output: PhantomData<T>,
+}
+
+impl<'de, T: SlugBytes> Visitor<'de> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "bytes")
+    }
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<T, E> {
+        return self.visit_byte_buf(v.to_vec())
+    }
+    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<T, E> {
+        let len = v.len();
+        return T::from_vec(v).map_err(|_| E::invalid_length(len, &self))
+    }
+    /// Formats without a bytes type (e.g. JSON arrays in a non human-readable mode) send a sequence of `u8`
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+
+        while let Some(b) = seq.next_element::<u8>()? {
+            bytes.push(b);
+        }
+        return self.visit_byte_buf(bytes)
+    }
+}
+
+macro_rules! serde_encoding {
+    ($name:ident, $encoding:ident, $doc:literal) => {
+        #[doc = $doc]
+        pub mod $name {
+            use serde::{Deserializer, Serializer};
+            use crate::SlugEncodings;
+            use alloc::vec::Vec;
+
+            /// Serializes bytes as an encoded string (raw bytes for binary formats)
+            pub fn serialize<S: Serializer, T: AsRef<[u8]> + ?Sized>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error> {
+                return super::serialize(SlugEncodings::$encoding, bytes.as_ref(), serializer)
+            }
+            /// Deserializes an encoded string (raw bytes for binary formats)
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+                return super::deserialize(SlugEncodings::$encoding, deserializer)
+            }
+
+            /// Fixed-size arrays (`[u8;N]`)
+            pub mod array {
+                use serde::{Deserializer, Serializer};
+                use crate::SlugEncodings;
+
+                /// Serializes an array as an encoded string (raw bytes for binary formats)
+                pub fn serialize<S: Serializer, const N: usize>(bytes: &[u8;N], serializer: S) -> Result<S::Ok, S::Error> {
+                    return super::super::serialize(SlugEncodings::$encoding, bytes, serializer)
+                }
+                /// Deserializes an encoded string (raw bytes for binary formats) of exactly `N` bytes
+                pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[u8;N], D::Error> {
+                    return super::super::deserialize(SlugEncodings::$encoding, deserializer)
+                }
+            }
+        }
+    };
+}
+
+serde_encoding!(hex, Hex, "Hexadecimal (Constant-Time)");
+serde_encoding!(base32, Base32, "Base32 (Constant-Time)");
+serde_encoding!(base32_unpadded, Base32unpadded, "Base32 Unpadded (Constant-Time)");
+serde_encoding!(base58, Base58, "Base58 (Constant-Time)");
+serde_encoding!(base58_fast, Base58fast, "Base58 Fast (Not Constant-Time)");
+serde_encoding!(base58_monero, Base58monero, "Base58 Monero (Constant-Time)");
+serde_encoding!(base64, Base64, "Base64 (Constant-Time)");
+serde_encoding!(base64_url, Base64urlsafe, "Base64 URL Safe (Constant-Time)");
+serde_encoding!(ascii85, Ascii85, "Ascii85 (Not Constant-Time)");
+serde_encoding!(z85, Z85, "Z85 (Not Constant-Time)");
+serde_encoding!(base85_rfc1924, Base85rfc1924, "Base85 RFC1924 (Not Constant-Time)");
+
+#[test]
+fn serde_readable_and_compact() {
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_tokens, Configure, Token};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Signed {
+        #[serde(with = "crate::serde::hex::array")]
+        key: [u8;2],
+        #[serde(with = "crate::serde::base64_url")]
+        signature: Vec<u8>,
+    }
+
+    let signed = Signed { key: [0xde, 0xad], signature: vec![0xfb, 0xff] };
+
+    assert_tokens(&signed.clone().readable(), &[
+        Token::Struct { name: "Signed", len: 2 },
+        Token::Str("key"),
+        Token::Str("dead"),
+        Token::Str("signature"),
+        Token::Str("-_8="),
+        Token::StructEnd,
+    ]);
+    assert_tokens(&signed.compact(), &[
+        Token::Struct { name: "Signed", len: 2 },
+        Token::Str("key"),
+        Token::Bytes(&[0xde, 0xad]),
+        Token::Str("signature"),
+        Token::Bytes(&[0xfb, 0xff]),
+        Token::StructEnd,
+    ]);
+}
+
+#[test]
+fn serde_errors() {
+    use serde::Deserialize;
+    use serde_test::{assert_de_tokens_error, Compact, Readable, Token};
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Key(#[serde(with = "crate::serde::base58::array")] [u8;4]);
+
+    assert_de_tokens_error::<Readable<Key>>(&[Token::NewtypeStruct { name: "Key" }, Token::Str("2VfUX0")], "invalid character '0' at byte 5 in Base58 input");
+    assert_de_tokens_error::<Readable<Key>>(&[Token::NewtypeStruct { name: "Key" }, Token::Str("2g")], "Base58 input decodes to 1 bytes (expected 4)");
+    assert_de_tokens_error::<Compact<Key>>(&[Token::NewtypeStruct { name: "Key" }, Token::Bytes(&[1, 2])], "invalid length 2, expected bytes");
+}
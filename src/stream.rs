@@ -0,0 +1,513 @@
+//! # Streaming (Push-Based)
+//!
+//! Hex, Base32, Base64, Z85, Base85 (RFC 1924) and Base58 (Monero) encode fixed-size blocks independently (1, 5, 3, 4 and 8 bytes), so they can be processed a block at a time. `SlugStreamEncoder` and `SlugStreamDecoder` accept input in fragments of any size through `update`, carry a partial block over to the next call, and only encode (and pad) or decode the final one in `finalize`.
+//!
+//! They do not depend on `std::io` and `update_to_slice`/`finalize_to_slice` write into caller-provided buffers, so they work in `no_std` (`update`/`finalize` return `String`/`Vec<u8>` with the `alloc` feature). The `std::io` and `tokio` adapters are built on them.
+//!
+//! Base58 (except Monero's) treats the whole input as one number, Ascii85 has variable-length groups (`z`) and Bech32 ends with a checksum over the whole string, so they return `SlugEncodingError::StreamingUnsupported`.
+
+use crate::errors::SlugEncodingError;
+use crate::{wipe, SlugEncodingUsage, SlugEncodings};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+/// The largest block (11 Base58 Monero characters).
+const MAX_BLOCK: usize = 11;
+
+/// Returns the size of a block of bytes and of its encoding.
+fn block_sizes(encoding: SlugEncodings) -> Result<(usize, usize), SlugEncodingError> {
+    match encoding {
+        SlugEncodings::Hex => Ok((1, 2)),
+        SlugEncodings::Base32 | SlugEncodings::Base32unpadded => Ok((5, 8)),
+        SlugEncodings::Base64 | SlugEncodings::Base64urlsafe => Ok((3, 4)),
+        SlugEncodings::Z85 | SlugEncodings::Base85rfc1924 => Ok((4, 5)),
+        SlugEncodings::Base58monero => Ok((8, 11)),
+        SlugEncodings::Base58 | SlugEncodings::Base58fast | SlugEncodings::Ascii85 | SlugEncodings::Bech32 | SlugEncodings::Bech32m => Err(SlugEncodingError::StreamingUnsupported { encoding }),
+    }
+}
+
+/// # SlugStreamEncoder
+///
+/// Encodes bytes pushed in fragments of any size. Each `update` returns the encoding of every whole block received so far, and `finalize` returns the (padded) encoding of the remaining partial block and resets the encoder.
+///
+/// The carried-over bytes are wiped when they are encoded and on drop.
+///
+/// ## Example Code
+///
+/// ```rust
+/// use slugencode::prelude::*;
+///
+/// fn main() {
+///     let mut encoder = SlugStreamEncoder::new(SlugEncodings::Base64).unwrap();
+///     let mut buf = [0u8;16];
+///     let mut encoded = String::new();
+///
+///     for fragment in [&b"sl"[..], b"ugen", b"code"] {
+///         encoded.push_str(encoder.update_to_slice(fragment, &mut buf).unwrap());
+///     }
+///     encoded.push_str(encoder.finalize_to_slice(&mut buf).unwrap());
+///
+///     assert_eq!(encoded, "c2x1Z2VuY29kZQ==");
+/// }
+/// ```
+pub struct SlugStreamEncoder {
+    usage: SlugEncodingUsage,
+    block: usize,
+    encoded_block: usize,
+    pending: [u8;MAX_BLOCK],
+    pending_len: usize,
+}
+
+impl SlugStreamEncoder {
+    /// Creates a new encoder for `usage` (a `SlugEncodingUsage`, whose Base58 alphabet is used, or a `SlugEncodings`). Returns `SlugEncodingError::StreamingUnsupported` for Base58, Ascii85 and Bech32.
+    pub fn new<U: Into<SlugEncodingUsage>>(usage: U) -> Result<Self, SlugEncodingError> {
+        let usage = usage.into();
+        let (block, encoded_block) = block_sizes(usage.get_encoding())?;
+
+        return Ok(Self {
+            usage,
+            block,
+            encoded_block,
+            pending: [0u8;MAX_BLOCK],
+            pending_len: 0,
+        })
+    }
+    /// Gets encoding
+    pub fn get_encoding(&self) -> SlugEncodings {
+        return self.usage.get_encoding()
+    }
+    /// # Update Length
+    ///
+    /// The size of the buffer `update_to_slice` needs for `input_len` more bytes.
+    pub fn update_len(&self, input_len: usize) -> usize {
+        return (self.pending_len + input_len) / self.block * self.encoded_block
+    }
+    /// # Finalize Length
+    ///
+    /// The size of the buffer `finalize_to_slice` needs.
+    pub fn finalize_len(&self) -> usize {
+        return self.usage.encoded_len(self.pending_len)
+    }
+    /// # Update To Slice
+    ///
+    /// Encodes every whole block of the carried-over bytes followed by `input` into `output` and carries over the rest.
+    ///
+    /// Returns `SlugEncodingError::BufferTooSmall` if `output` is shorter than `update_len(input.len())`.
+    pub fn update_to_slice<'a>(&mut self, input: &[u8], output: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+        let required = self.update_len(input.len());
+
+        if output.len() < required {
+            return Err(SlugEncodingError::BufferTooSmall { encoding: self.get_encoding(), required })
+        }
+
+        let (_, written) = self.update_partial(input, output)?;
+        return as_str(&output[..written], self.get_encoding())
+    }
+    /// # Finalize To Slice
+    ///
+    /// Encodes (and pads) the carried-over bytes into `output` and resets the encoder.
+    ///
+    /// Returns `SlugEncodingError::BufferTooSmall` if `output` is shorter than `finalize_len()`.
+    pub fn finalize_to_slice<'a>(&mut self, output: &'a mut [u8]) -> Result<&'a str, SlugEncodingError> {
+        let written = self.finish(output)?;
+        return as_str(&output[..written], self.get_encoding())
+    }
+    /// Encodes every whole block received so far (see `update_to_slice`).
+    #[cfg(feature = "alloc")]
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) -> Result<String, SlugEncodingError> {
+        let input = input.as_ref();
+        let mut output = vec![0u8; self.update_len(input.len())];
+
+        let len = self.update_to_slice(input, &mut output)?.len();
+        return into_string(output, len, self.get_encoding())
+    }
+    /// Encodes (and pads) the remaining bytes and resets the encoder (see `finalize_to_slice`).
+    #[cfg(feature = "alloc")]
+    pub fn finalize(&mut self) -> Result<String, SlugEncodingError> {
+        let mut output = vec![0u8; self.finalize_len()];
+
+        let len = self.finalize_to_slice(&mut output)?.len();
+        return into_string(output, len, self.get_encoding())
+    }
+    /// Encodes the whole blocks of `input` that fit in `output` and carries over a trailing partial block, returning how many bytes were consumed and written.
+    ///
+    /// `output` must hold at least one encoded block.
+    pub(crate) fn update_partial(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize), SlugEncodingError> {
+        let mut consumed = 0usize;
+        let mut written = 0usize;
+
+        if self.pending_len > 0 {
+            let take = (self.block - self.pending_len).min(input.len());
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&input[..take]);
+            self.pending_len += take;
+            consumed = take;
+
+            if self.pending_len < self.block {
+                return Ok((consumed, written))
+            }
+
+            written = self.usage.encode_to_slice(&self.pending[..self.block], output)?.len();
+            self.reset_pending();
+        }
+
+        let blocks = ((input.len() - consumed) / self.block).min((output.len() - written) / self.encoded_block);
+
+        if blocks > 0 {
+            let len = blocks * self.block;
+            written += self.usage.encode_to_slice(&input[consumed..consumed + len], &mut output[written..])?.len();
+            consumed += len;
+        }
+
+        let rest = input.len() - consumed;
+
+        if rest < self.block {
+            self.pending[..rest].copy_from_slice(&input[consumed..]);
+            self.pending_len = rest;
+            consumed += rest;
+        }
+        return Ok((consumed, written))
+    }
+    /// Encodes (and pads) the carried-over bytes and resets the encoder, returning how many bytes were written.
+    pub(crate) fn finish(&mut self, output: &mut [u8]) -> Result<usize, SlugEncodingError> {
+        let result = self.usage.encode_to_slice(&self.pending[..self.pending_len], output).map(|s| s.len());
+        self.reset_pending();
+        return result
+    }
+    fn reset_pending(&mut self) {
+        wipe(&mut self.pending);
+        self.pending_len = 0;
+    }
+}
+
+impl Drop for SlugStreamEncoder {
+    fn drop(&mut self) {
+        wipe(&mut self.pending);
+    }
+}
+
+/// # SlugStreamDecoder
+///
+/// Decodes characters pushed in fragments of any size. Each `update` returns the bytes of every whole block received so far, and `finalize` decodes the remaining partial block (only valid for unpadded encodings) and resets the decoder.
+///
+/// Invalid characters are reported with their position in the whole stream, and anything after a padded block is rejected with `SlugEncodingError::InvalidPadding`.
+///
+/// ## Example Code
+///
+/// ```rust
+/// use slugencode::prelude::*;
+///
+/// fn main() {
+///     let mut decoder = SlugStreamDecoder::new(SlugEncodings::Hex).unwrap();
+///     let mut buf = [0u8;16];
+///     let mut decoded = Vec::new();
+///
+///     for fragment in ["d", "ead", "beef"] {
+///         decoded.extend_from_slice(decoder.update_to_slice(fragment, &mut buf).unwrap());
+///     }
+///     decoded.extend_from_slice(decoder.finalize_to_slice(&mut buf).unwrap());
+///
+///     assert_eq!(decoded, [0xde, 0xad, 0xbe, 0xef]);
+/// }
+/// ```
+pub struct SlugStreamDecoder {
+    usage: SlugEncodingUsage,
+    block: usize,
+    decoded_block: usize,
+    pending: [u8;MAX_BLOCK],
+    pending_len: usize,
+    /// Characters decoded so far (to report positions in the whole stream)
+    position: usize,
+    /// A padded block was decoded, so nothing may follow it
+    padded: bool,
+}
+
+impl SlugStreamDecoder {
+    /// Creates a new decoder for `usage` (a `SlugEncodingUsage`, whose Base58 alphabet is used, or a `SlugEncodings`). Returns `SlugEncodingError::StreamingUnsupported` for Base58, Ascii85 and Bech32.
+    pub fn new<U: Into<SlugEncodingUsage>>(usage: U) -> Result<Self, SlugEncodingError> {
+        let usage = usage.into();
+        let (decoded_block, block) = block_sizes(usage.get_encoding())?;
+
+        return Ok(Self {
+            usage,
+            block,
+            decoded_block,
+            pending: [0u8;MAX_BLOCK],
+            pending_len: 0,
+            position: 0,
+            padded: false,
+        })
+    }
+    /// Gets encoding
+    pub fn get_encoding(&self) -> SlugEncodings {
+        return self.usage.get_encoding()
+    }
+    /// # Update Length
+    ///
+    /// The size of the buffer `update_to_slice` needs for `input_len` more characters.
+    pub fn update_len(&self, input_len: usize) -> usize {
+        return (self.pending_len + input_len) / self.block * self.decoded_block
+    }
+    /// # Finalize Length
+    ///
+    /// The size of the buffer `finalize_to_slice` needs.
+    pub fn finalize_len(&self) -> usize {
+        return self.usage.decoded_len(self.pending_len)
+    }
+    /// # Update To Slice
+    ///
+    /// Decodes every whole block of the carried-over characters followed by `input` into `output` and carries over the rest.
+    ///
+    /// Returns `SlugEncodingError::BufferTooSmall` if `output` is shorter than `update_len(input.len())`.
+    pub fn update_to_slice<'a, T: AsRef<[u8]>>(&mut self, input: T, output: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+        let input = input.as_ref();
+        let required = self.update_len(input.len());
+
+        if output.len() < required {
+            return Err(SlugEncodingError::BufferTooSmall { encoding: self.get_encoding(), required })
+        }
+
+        let (_, written) = self.update_partial(input, output)?;
+        return Ok(&output[..written])
+    }
+    /// # Finalize To Slice
+    ///
+    /// Decodes the carried-over characters into `output` and resets the decoder.
+    ///
+    /// Returns `SlugEncodingError::BufferTooSmall` if `output` is shorter than `finalize_len()`.
+    pub fn finalize_to_slice<'a>(&mut self, output: &'a mut [u8]) -> Result<&'a [u8], SlugEncodingError> {
+        let written = self.finish(output)?;
+        return Ok(&output[..written])
+    }
+    /// Decodes every whole block received so far (see `update_to_slice`).
+    #[cfg(feature = "alloc")]
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) -> Result<Vec<u8>, SlugEncodingError> {
+        let input = input.as_ref();
+        let mut output = vec![0u8; self.update_len(input.len())];
+
+        let len = self.update_to_slice(input, &mut output)?.len();
+        output.truncate(len);
+        return Ok(output)
+    }
+    /// Decodes the remaining characters and resets the decoder (see `finalize_to_slice`).
+    #[cfg(feature = "alloc")]
+    pub fn finalize(&mut self) -> Result<Vec<u8>, SlugEncodingError> {
+        let mut output = vec![0u8; self.finalize_len()];
+
+        let len = self.finalize_to_slice(&mut output)?.len();
+        output.truncate(len);
+        return Ok(output)
+    }
+    /// Decodes the whole blocks of `input` that fit in `output` and carries over a trailing partial block, returning how many characters were consumed and bytes written.
+    ///
+    /// `output` must hold at least one decoded block (`min_output()`).
+    pub(crate) fn update_partial(&mut self, input: &[u8], output: &mut [u8]) -> Result<(usize, usize), SlugEncodingError> {
+        let mut consumed = 0usize;
+        let mut written = 0usize;
+
+        if self.padded && !input.is_empty() {
+            return Err(SlugEncodingError::InvalidPadding { encoding: self.get_encoding(), source: None })
+        }
+
+        if self.pending_len > 0 {
+            let take = (self.block - self.pending_len).min(input.len());
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&input[..take]);
+            self.pending_len += take;
+            consumed = take;
+
+            if self.pending_len < self.block {
+                return Ok((consumed, written))
+            }
+
+            let mut pending = self.pending;
+            let result = self.decode(&pending[..self.block], output);
+            wipe(&mut pending);
+            self.reset_pending();
+            written = result?;
+        }
+
+        let blocks = ((input.len() - consumed) / self.block).min((output.len() - written) / self.decoded_block);
+
+        if blocks > 0 && !self.padded {
+            let len = blocks * self.block;
+            written += self.decode(&input[consumed..consumed + len], &mut output[written..])?;
+            consumed += len;
+        }
+
+        if self.padded && consumed < input.len() {
+            return Err(SlugEncodingError::InvalidPadding { encoding: self.get_encoding(), source: None })
+        }
+
+        let rest = input.len() - consumed;
+
+        if rest < self.block {
+            self.pending[..rest].copy_from_slice(&input[consumed..]);
+            self.pending_len = rest;
+            consumed += rest;
+        }
+        return Ok((consumed, written))
+    }
+    /// Decodes the carried-over characters and resets the decoder, returning how many bytes were written.
+    pub(crate) fn finish(&mut self, output: &mut [u8]) -> Result<usize, SlugEncodingError> {
+        let mut pending = self.pending;
+        let result = match self.pending_len {
+            0 => Ok(0),
+            len => self.decode(&pending[..len], output),
+        };
+
+        wipe(&mut pending);
+        self.reset_pending();
+        self.position = 0;
+        self.padded = false;
+        return result
+    }
+    /// The size of one decoded block.
+    #[cfg(feature = "std")]
+    pub(crate) fn min_output(&self) -> usize {
+        return self.decoded_block
+    }
+    fn decode(&mut self, encoded: &[u8], output: &mut [u8]) -> Result<usize, SlugEncodingError> {
+        let written = self.usage.decode_to_slice(encoded, output).map_err(|e| at_position(e, self.position))?.len();
+
+        self.position += encoded.len();
+        self.padded = is_padded(self.get_encoding(), encoded);
+        return Ok(written)
+    }
+    fn reset_pending(&mut self) {
+        wipe(&mut self.pending);
+        self.pending_len = 0;
+    }
+}
+
+impl Drop for SlugStreamDecoder {
+    fn drop(&mut self) {
+        wipe(&mut self.pending);
+    }
+}
+
+/// Whether `encoded` ends with padding (which must be the end of the stream).
+fn is_padded(encoding: SlugEncodings, encoded: &[u8]) -> bool {
+    match encoding {
+        SlugEncodings::Base32 | SlugEncodings::Base64 | SlugEncodings::Base64urlsafe => encoded.last() == Some(&b'='),
+        _ => false,
+    }
+}
+
+/// Shifts the position of an invalid character (and the length of a final block) from the start of a block to the start of the stream.
+fn at_position(e: SlugEncodingError, position: usize) -> SlugEncodingError {
+    match e {
+        SlugEncodingError::InvalidCharacter { encoding, index, character, source } => SlugEncodingError::InvalidCharacter { encoding, index: index + position, character, source },
+        SlugEncodingError::InvalidLength { encoding, length, source } => SlugEncodingError::InvalidLength { encoding, length: length + position, source },
+        e => e,
+    }
+}
+
+/// Every encoding with blocks is ASCII.
+fn as_str(encoded: &[u8], encoding: SlugEncodings) -> Result<&str, SlugEncodingError> {
+    return core::str::from_utf8(encoded).map_err(|_| SlugEncodingError::EncodingError { encoding, source: None })
+}
+
+#[cfg(feature = "alloc")]
+fn into_string(mut encoded: Vec<u8>, len: usize, encoding: SlugEncodings) -> Result<String, SlugEncodingError> {
+    encoded.truncate(len);
+    return String::from_utf8(encoded).map_err(|_| SlugEncodingError::EncodingError { encoding, source: None })
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn stream_fragments() {
+    let data: Vec<u8> = (0..300u32).map(|i| (i * 31 + i / 5) as u8).collect();
+    let encodings = [
+        SlugEncodings::Hex,
+        SlugEncodings::Base32,
+        SlugEncodings::Base32unpadded,
+        SlugEncodings::Base64,
+        SlugEncodings::Base64urlsafe,
+        SlugEncodings::Z85,
+        SlugEncodings::Base85rfc1924,
+        SlugEncodings::Base58monero,
+    ];
+
+    for encoding in encodings {
+        let usage = SlugEncodingUsage::new(encoding);
+        let mut encoder = SlugStreamEncoder::new(encoding).unwrap();
+        let mut decoder = SlugStreamDecoder::new(encoding).unwrap();
+
+        // The encoders are reused after `finalize`
+        for len in [0, 1, 4, 7, 8, 100, 300] {
+            let len = if encoding == SlugEncodings::Z85 { len / 4 * 4 } else { len };
+
+            for fragment in [1, 2, 3, 11] {
+                let mut encoded = String::new();
+                for chunk in data[..len].chunks(fragment) {
+                    encoded.push_str(&encoder.update(chunk).unwrap());
+                }
+                encoded.push_str(&encoder.finalize().unwrap());
+                assert_eq!(encoded, usage.encode(&data[..len]).unwrap());
+
+                let mut decoded = Vec::new();
+                for chunk in encoded.as_bytes().chunks(fragment) {
+                    decoded.extend_from_slice(&decoder.update(chunk).unwrap());
+                }
+                decoded.extend_from_slice(&decoder.finalize().unwrap());
+                assert_eq!(decoded, &data[..len]);
+            }
+        }
+    }
+
+    for encoding in [SlugEncodings::Base58, SlugEncodings::Base58fast, SlugEncodings::Ascii85, SlugEncodings::Bech32, SlugEncodings::Bech32m] {
+        assert!(SlugStreamEncoder::new(encoding).is_err());
+        assert!(SlugStreamDecoder::new(encoding).is_err());
+    }
+}
+
+#[test]
+fn stream_slices() {
+    let mut encoder = SlugStreamEncoder::new(SlugEncodings::Base32).unwrap();
+    let mut buf = [0u8;8];
+
+    assert_eq!(encoder.update_to_slice(b"foo", &mut buf).unwrap(), "");
+    assert_eq!(encoder.update_len(3), 8);
+    assert_eq!(encoder.update_to_slice(b"bar", &mut buf[..4]), Err(SlugEncodingError::BufferTooSmall { encoding: SlugEncodings::Base32, required: 8 }));
+    assert_eq!(encoder.update_to_slice(b"bar", &mut buf).unwrap(), "mzxw6ytb");
+    assert_eq!(encoder.finalize_to_slice(&mut buf).unwrap(), "oi======");
+
+    // Positions are counted from the start of the stream, and nothing may follow padding
+    let mut decoder = SlugStreamDecoder::new(SlugEncodings::Base64).unwrap();
+    assert_eq!(decoder.update_to_slice("QUJD", &mut buf).unwrap(), b"ABC");
+    let e = decoder.update_to_slice("QU*D", &mut buf).unwrap_err();
+    assert_eq!((e.position(), e.character()), (Some(6), Some('*')));
+
+    let mut decoder = SlugStreamDecoder::new(SlugEncodings::Base64).unwrap();
+    assert_eq!(decoder.update_to_slice("QQ=", &mut buf).unwrap(), b"");
+    assert_eq!(decoder.update_to_slice("=", &mut buf).unwrap(), b"A");
+    assert!(matches!(decoder.update_to_slice("QUJD", &mut buf), Err(SlugEncodingError::InvalidPadding { .. })));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn stream_alphabets() {
+    use crate::base58::Base58Alphabet;
+
+    let data: Vec<u8> = (0..50u8).map(|i| i.wrapping_mul(73)).collect();
+
+    for alphabet in [Base58Alphabet::RIPPLE, Base58Alphabet::FLICKR] {
+        let usage = SlugEncodingUsage::new(SlugEncodings::Base58monero).with_alphabet(alphabet);
+        let expected = usage.encode(&data).unwrap();
+        assert_ne!(expected, SlugEncodingUsage::new(SlugEncodings::Base58monero).encode(&data).unwrap());
+
+        let mut encoder = usage.stream_encoder().unwrap();
+        let mut encoded = String::new();
+        for chunk in data.chunks(3) {
+            encoded.push_str(&encoder.update(chunk).unwrap());
+        }
+        encoded.push_str(&encoder.finalize().unwrap());
+        assert_eq!(encoded, expected);
+
+        let mut decoder = usage.stream_decoder().unwrap();
+        let mut decoded = decoder.update(&encoded[..20]).unwrap();
+        decoded.extend_from_slice(&decoder.update(&encoded[20..]).unwrap());
+        decoded.extend_from_slice(&decoder.finalize().unwrap());
+        assert_eq!(decoded, data);
+    }
+}
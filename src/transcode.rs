@@ -0,0 +1,231 @@
+//! # Transcoding
+//!
+//! Requires the `alloc` feature.
+//!
+//! Converts an encoded string from one encoding to another without handing the decoded bytes back to the caller. `SlugEncodingUsage::transcode` converts a whole string at once, while `SlugTranscoder` accepts input in fragments of any size.
+//!
+//! Both sides are a `SlugEncodingUsage` (or a bare `SlugEncodings`), so the human-readable prefix of Bech32 and Bech32m (`with_hrp`) and the Base58 alphabet (`with_alphabet`) are used when reading and writing. Writing Bech32 without a prefix returns `SlugEncodingError::InvalidHrp`.
+//!
+//! When both encodings can be streamed (Hex, Base32, Base64, Z85, Base85 (RFC 1924) and Base58 (Monero)), `SlugTranscoder` decodes and re-encodes a block at a time through a small intermediate buffer. Otherwise (Base58, Ascii85) the input is collected and converted by `finalize`.
+//!
+//! The decoded bytes are wiped once they are re-encoded (with `zeroize` when the `zeroize` feature is on, in which case the one-shot conversion also decodes into a `Zeroizing` buffer).
+
+use crate::errors::SlugEncodingError;
+use crate::stream::{SlugStreamDecoder, SlugStreamEncoder};
+use crate::{wipe, SlugEncodingUsage};
+
+use alloc::{string::String, vec::Vec};
+
+/// Size of the intermediate (decoded) buffer.
+const BUFFER_SIZE: usize = 1024;
+
+/// # SlugTranscoder
+///
+/// Converts an encoded string pushed in fragments of any size from one encoding to another. Each `update` returns the re-encoding of every whole block received so far (nothing when either encoding cannot be streamed), and `finalize` returns the rest and resets the transcoder.
+///
+/// ## Example Code
+///
+/// ```rust
+/// use slugencode::prelude::*;
+///
+/// fn main() {
+///     let mut transcoder = SlugTranscoder::new(SlugEncodings::Hex, SlugEncodings::Base64);
+///     assert!(transcoder.is_streaming());
+///
+///     let mut encoded = String::new();
+///
+///     for fragment in ["736c", "7567656e", "636f6465"] {
+///         encoded.push_str(&transcoder.update(fragment).unwrap());
+///     }
+///     encoded.push_str(&transcoder.finalize().unwrap());
+///
+///     assert_eq!(encoded, "c2x1Z2VuY29kZQ==");
+/// }
+/// ```
+pub struct SlugTranscoder {
+    from: SlugEncodingUsage,
+    to: SlugEncodingUsage,
+    state: TranscoderState,
+}
+
+// Only one per transcoder, so the streaming state is kept inline
+#[allow(clippy::large_enum_variant)]
+enum TranscoderState {
+    /// Both encodings are processed a block at a time
+    Streaming {
+        decoder: SlugStreamDecoder,
+        encoder: SlugStreamEncoder,
+    },
+    /// The encoded input collected until `finalize`
+    Buffered(Vec<u8>),
+}
+
+impl SlugTranscoder {
+    /// Creates a new transcoder from `from` to `to` (a `SlugEncodingUsage` or a `SlugEncodings`)
+    pub fn new<F: Into<SlugEncodingUsage>, U: Into<SlugEncodingUsage>>(from: F, to: U) -> Self {
+        let (from, to) = (from.into(), to.into());
+        let state = match (SlugStreamDecoder::new(from), SlugStreamEncoder::new(to)) {
+            (Ok(decoder), Ok(encoder)) => TranscoderState::Streaming { decoder, encoder },
+            _ => TranscoderState::Buffered(Vec::new()),
+        };
+
+        return Self { from, to, state }
+    }
+    /// Gets the usage the input is decoded with
+    pub fn get_from(&self) -> SlugEncodingUsage {
+        return self.from
+    }
+    /// Gets the usage the output is encoded with
+    pub fn get_to(&self) -> SlugEncodingUsage {
+        return self.to
+    }
+    /// Whether the input is converted a block at a time (both encodings can be streamed)
+    pub fn is_streaming(&self) -> bool {
+        return matches!(self.state, TranscoderState::Streaming { .. })
+    }
+    /// # Update
+    ///
+    /// Converts every whole block of the input received so far. Invalid characters are reported with their position in the whole input.
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) -> Result<String, SlugEncodingError> {
+        let input = input.as_ref();
+
+        match &mut self.state {
+            TranscoderState::Streaming { decoder, encoder } => {
+                let mut decoded = [0u8;BUFFER_SIZE];
+                let result = pump(decoder, encoder, input, &mut decoded);
+                wipe(&mut decoded);
+                return result
+            }
+            TranscoderState::Buffered(pending) => {
+                pending.extend_from_slice(input);
+                return Ok(String::new())
+            }
+        }
+    }
+    /// # Finalize
+    ///
+    /// Converts the rest of the input and resets the transcoder (also on errors).
+    pub fn finalize(&mut self) -> Result<String, SlugEncodingError> {
+        let result = match &mut self.state {
+            TranscoderState::Streaming { decoder, encoder } => {
+                let mut decoded = [0u8;BUFFER_SIZE];
+                let result = decoder.finalize_to_slice(&mut decoded).and_then(|last| {
+                    let mut encoded = encoder.update(last)?;
+                    encoded.push_str(&encoder.finalize()?);
+                    Ok(encoded)
+                });
+                wipe(&mut decoded);
+                result
+            }
+            TranscoderState::Buffered(pending) => SlugEncodingUsage::transcode(&pending[..], self.from, self.to),
+        };
+
+        *self = Self::new(self.from, self.to);
+        return result
+    }
+}
+
+impl Drop for SlugTranscoder {
+    fn drop(&mut self) {
+        if let TranscoderState::Buffered(pending) = &mut self.state {
+            wipe(pending);
+        }
+    }
+}
+
+/// Decodes `input` into `decoded` a buffer at a time and re-encodes it.
+fn pump(decoder: &mut SlugStreamDecoder, encoder: &mut SlugStreamEncoder, mut input: &[u8], decoded: &mut [u8]) -> Result<String, SlugEncodingError> {
+    let mut output = String::new();
+
+    while !input.is_empty() {
+        let (consumed, written) = decoder.update_partial(input, decoded)?;
+        output.push_str(&encoder.update(&decoded[..written])?);
+        wipe(&mut decoded[..written]);
+        input = &input[consumed..];
+    }
+    return Ok(output)
+}
+
+#[test]
+fn transcode_fragments() {
+    use crate::SlugEncodings;
+
+    let data: Vec<u8> = (0..2500u32).map(|i| (i * 5 + i / 11) as u8).collect();
+
+    let is_bech32 = |encoding| matches!(encoding, SlugEncodings::Bech32 | SlugEncodings::Bech32m);
+
+    for from in crate::test_usages() {
+        for to in crate::test_usages() {
+            let (from_encoding, to_encoding) = (from.get_encoding(), to.get_encoding());
+
+            for len in [0, 1, 4, 7, 8, 300] {
+                // Z85 only encodes multiples of 4 bytes and Bech32 strings are at most 90 characters
+                let len = if from_encoding == SlugEncodings::Z85 || to_encoding == SlugEncodings::Z85 { len / 4 * 4 } else { len };
+                let len = if is_bech32(from_encoding) || is_bech32(to_encoding) { len.min(40) } else { len };
+                let encoded = from.encode(&data[..len]).unwrap();
+                let expected = to.encode(&data[..len]).unwrap();
+
+                assert_eq!(SlugEncodingUsage::transcode(&encoded, from, to).unwrap(), expected, "{} -> {}", from_encoding, to_encoding);
+
+                let mut transcoder = SlugTranscoder::new(from, to);
+                let mut transcoded = String::new();
+                for chunk in encoded.as_bytes().chunks(1 + len % 5) {
+                    transcoded.push_str(&transcoder.update(chunk).unwrap());
+                }
+                transcoded.push_str(&transcoder.finalize().unwrap());
+                assert_eq!(transcoded, expected, "{} -> {}", from_encoding, to_encoding);
+            }
+        }
+    }
+
+    assert!(SlugTranscoder::new(SlugEncodings::Base32, SlugEncodings::Z85).is_streaming());
+    assert!(!SlugTranscoder::new(SlugEncodings::Base58, SlugEncodings::Hex).is_streaming());
+    assert!(!SlugTranscoder::new(SlugEncodings::Hex, SlugEncodings::Ascii85).is_streaming());
+}
+
+#[test]
+fn transcode_usages() {
+    use crate::base58::Base58Alphabet;
+    use crate::SlugEncodings;
+
+    // The Ripple account zero, read with the Ripple alphabet and written with Bitcoin's
+    let ripple = SlugEncodingUsage::new(SlugEncodings::Base58).with_alphabet(Base58Alphabet::RIPPLE);
+    assert_eq!(SlugEncodingUsage::transcode("rrrrrrrrrrrrrrrrrrrrrhoLvTp", ripple, SlugEncodings::Base58).unwrap(), "1111111111111111111114oLvT2");
+    assert_eq!(SlugEncodingUsage::transcode("1111111111111111111114oLvT2", SlugEncodings::Base58, ripple).unwrap(), "rrrrrrrrrrrrrrrrrrrrrhoLvTp");
+
+    // Bech32 is written with the prefix and the prefix is checked when reading
+    let age = SlugEncodingUsage::new(SlugEncodings::Bech32).with_hrp("age").unwrap();
+    let encoded = SlugEncodingUsage::transcode("deadbeef", SlugEncodings::Hex, age).unwrap();
+    assert_eq!(SlugEncodingUsage::transcode(&encoded, age, SlugEncodings::Hex).unwrap(), "deadbeef");
+
+    let npub = SlugEncodingUsage::new(SlugEncodings::Bech32).with_hrp("npub").unwrap();
+    assert!(matches!(SlugEncodingUsage::transcode(&encoded, npub, SlugEncodings::Hex), Err(SlugEncodingError::InvalidHrp { .. })));
+
+    let mut transcoder = SlugTranscoder::new(age, ripple);
+    transcoder.update(&encoded).unwrap();
+    assert_eq!(transcoder.finalize().unwrap(), ripple.encode([0xde, 0xad, 0xbe, 0xef]).unwrap());
+    assert_eq!((transcoder.get_from(), transcoder.get_to()), (age, ripple));
+}
+
+#[test]
+fn transcode_errors() {
+    use crate::SlugEncodings;
+
+    let mut transcoder = SlugTranscoder::new(SlugEncodings::Hex, SlugEncodings::Base64);
+    transcoder.update("deadbeef").unwrap();
+    let e = transcoder.update("00zz").unwrap_err();
+    assert_eq!((e.position(), e.character()), (Some(10), Some('z')));
+
+    // A truncated block fails in `finalize`, which resets the transcoder
+    let mut transcoder = SlugTranscoder::new(SlugEncodings::Hex, SlugEncodings::Base64);
+    transcoder.update("dea").unwrap();
+    assert!(transcoder.finalize().is_err());
+    assert_eq!(transcoder.update("dead").unwrap(), "");
+    assert_eq!(transcoder.finalize().unwrap(), "3q0=");
+
+    let mut transcoder = SlugTranscoder::new(SlugEncodings::Base58, SlugEncodings::Hex);
+    transcoder.update("2NEpo7TZRRrLZSi2U0").unwrap();
+    assert!(matches!(transcoder.finalize(), Err(SlugEncodingError::InvalidCharacter { index: 17, .. })));
+
+    assert_eq!(SlugEncodingUsage::transcode("2NEpo7TZRRrLZSi2U", SlugEncodings::Base58, SlugEncodings::Hex).unwrap(), "48656c6c6f20576f726c6421");
+}
@@ -0,0 +1,72 @@
+//! # Constant-Time Verification
+//!
+//! Comparing a received token with `==` (on strings or bytes) returns at the first difference, which leaks how much of it was right. `SlugEncodingUsage::verify` decodes the token and compares it with the expected bytes using `subtle`, returning a `subtle::Choice` instead of a `bool`.
+//!
+//! For the constant-time encodings (Hex, Base32, Base58, Base58 (Monero) and Base64) decoding valid input and the comparison do not branch on the data. The lengths, and whether the input is valid at all, are not hidden (an invalid token is rejected without comparing it).
+//!
+//! `SlugCtEq` adds `ct_eq_bytes` to anything that is `AsRef<[u8]>` (`Vec<u8>`, `[u8;N]`, `Zeroizing<Vec<u8>>`, ...) to compare decoded outputs the same way.
+
+use subtle::{Choice, ConstantTimeEq};
+
+/// # SlugCtEq
+///
+/// Constant-time equality for decoded outputs.
+///
+/// ## Example Code
+///
+/// ```rust
+/// use slugencode::prelude::*;
+/// use slugencode::verify::SlugCtEq;
+///
+/// # #[cfg(feature = "alloc")]
+/// fn main() {
+///     let decoded = SlugEncodingUsage::new(SlugEncodings::Hex).decode("deadbeef").unwrap();
+///
+///     assert!(bool::from(decoded.ct_eq_bytes([0xde, 0xad, 0xbe, 0xef])));
+///     assert!(!bool::from(decoded.ct_eq_bytes([0xde, 0xad, 0xbe, 0xee])));
+/// }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+pub trait SlugCtEq {
+    /// Compares the bytes in constant-time (only the lengths are compared in variable time)
+    fn ct_eq_bytes<T: AsRef<[u8]>>(&self, other: T) -> Choice;
+}
+
+impl<B: AsRef<[u8]> + ?Sized> SlugCtEq for B {
+    fn ct_eq_bytes<T: AsRef<[u8]>>(&self, other: T) -> Choice {
+        return ct_eq(self, other)
+    }
+}
+
+/// Compares two byte strings in constant-time (only the lengths are compared in variable time).
+pub fn ct_eq<A: AsRef<[u8]> + ?Sized, B: AsRef<[u8]>>(a: &A, b: B) -> Choice {
+    return a.as_ref().ct_eq(b.as_ref())
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn verify_encoded() {
+    use crate::{SlugEncodingUsage, SlugEncodings};
+
+    let expected = [0xfbu8, 0xff, 0x00, 0x10];
+
+    for usage in crate::test_usages() {
+        let (encoded, encoding) = (usage.encode(expected).unwrap(), usage.get_encoding());
+
+        assert!(bool::from(usage.verify(expected, &encoded)), "{}", encoding);
+        assert!(!bool::from(usage.verify([0xfbu8, 0xff, 0x00, 0x11], &encoded)), "{}", encoding);
+        assert!(!bool::from(usage.verify(&expected[..3], &encoded)), "{}", encoding);
+    }
+
+    let usage = SlugEncodingUsage::new(SlugEncodings::Hex);
+    assert!(bool::from(usage.verify([0xde, 0xad], "DEAD")));
+    assert!(!bool::from(usage.verify([0xde, 0xad], "dea")));
+    assert!(!bool::from(usage.verify([0xde, 0xad], "deaz")));
+    assert!(bool::from(usage.verify([], "")));
+
+    let decoded = usage.decode("dead").unwrap();
+    assert!(bool::from(decoded.ct_eq_bytes([0xde, 0xad])));
+    assert!(bool::from([1u8, 2].ct_eq_bytes(vec![1, 2])));
+    assert!(!bool::from(ct_eq(&[1u8, 2][..], [1u8])));
+}